@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use chiptool::ir::Array;
+use lazy_regex::regex;
+use stm32_data_serde::chip::{Boot, LowPowerMode, WakeupPin, flash, wakeup_pin};
+
+use crate::chips::ChipGroup;
+use crate::registers::Registers;
+use crate::util::new_regex_map;
+
+/// Deepest low-power mode the wakeup pins can exit, by chip. The first match wins.
+#[rustfmt::skip]
+const WAKEUP_MODES: &[(&str, LowPowerMode)] = &[
+    ("STM32(C0|G0|G4|L4|L5|U0|U3|U5|WB[0-9]|WL).*", LowPowerMode::Shutdown),
+    ("STM32.*",                                     LowPowerMode::Standby),
+];
+
+#[derive(Debug, Default)]
+struct WakeupLine {
+    enable: Option<wakeup_pin::Field>,
+    polarity: Option<wakeup_pin::Field>,
+}
+
+#[derive(Debug)]
+pub struct ParsedBoot {
+    /// FLASH version -> boot option bits
+    option_bits: HashMap<String, Vec<flash::OptionBit>>,
+    /// PWR version -> wakeup line -> enable and polarity fields
+    wakeup_lines: HashMap<String, HashMap<u8, WakeupLine>>,
+    wakeup_modes: regex_map::RegexMap<LowPowerMode>,
+}
+
+impl ParsedBoot {
+    /// Parse the boot option bits from the `flash_xx` yaml files and the wakeup pin fields from
+    /// the `pwr_xx` yaml files in `data/registers`.
+    pub fn parse(registers: &Registers) -> anyhow::Result<Self> {
+        let mut option_bits = HashMap::new();
+        let mut wakeup_lines = HashMap::new();
+
+        for (name, ir) in &registers.registers {
+            if let Some(version) = name.strip_prefix("flash_") {
+                let mut bits: Vec<flash::OptionBit> = Vec::new();
+                for (reg, body) in &ir.fieldsets {
+                    for field in &body.fields {
+                        if regex!(r"^n?(SW)?BOOT(0|1|_SEL)$").is_match(&field.name) {
+                            bits.push(flash::OptionBit {
+                                register: reg.clone(),
+                                field: field.name.clone(),
+                            });
+                        }
+                    }
+                }
+                // The same bit is usually in several option registers, e.g. `OPTSR_CUR` and `OPTSR_PRG`.
+                bits.sort_by(|a, b| (&a.field, &a.register).cmp(&(&b.field, &b.register)));
+                bits.dedup_by(|a, b| a.field == b.field);
+                option_bits.insert(version.to_string(), bits);
+            } else if let Some(version) = name.strip_prefix("pwr_") {
+                let mut lines: HashMap<u8, WakeupLine> = HashMap::new();
+                for (reg, body) in &ir.fieldsets {
+                    // `C2CR3` and friends configure the wakeup lines for the second core.
+                    if reg.starts_with("C2") {
+                        continue;
+                    }
+                    for field in &body.fields {
+                        let Some(m) = regex!(r"^(EWUP|WUPEN|WKUPEN|WP|WUPP|WKUPP)(\d*)$").captures(&field.name) else {
+                            continue;
+                        };
+                        let is_enable = matches!(&m[1], "EWUP" | "WUPEN" | "WKUPEN");
+                        let first: u8 = m[2].parse().unwrap_or(1);
+
+                        let fields: Vec<(u8, Option<u32>)> = match &field.array {
+                            None => vec![(first, None)],
+                            Some(Array::Regular(a)) => (0..a.len).map(|i| (first + i as u8, Some(i))).collect(),
+                            Some(Array::Cursed(a)) => (0..a.offsets.len() as u32)
+                                .map(|i| (first + i as u8, Some(i)))
+                                .collect(),
+                        };
+
+                        for (line, index) in fields {
+                            let val = wakeup_pin::Field {
+                                register: reg.clone(),
+                                field: field.name.clone(),
+                                index,
+                            };
+                            let entry = lines.entry(line).or_default();
+                            if is_enable {
+                                entry.enable = Some(val);
+                            } else {
+                                entry.polarity = Some(val);
+                            }
+                        }
+                    }
+                }
+                wakeup_lines.insert(version.to_string(), lines);
+            }
+        }
+
+        Ok(Self {
+            option_bits,
+            wakeup_lines,
+            wakeup_modes: new_regex_map(WAKEUP_MODES.iter().cloned()),
+        })
+    }
+
+    /// Get the boot pins and option bits of a chip.
+    ///
+    /// BOOT0 is either a dedicated pin, or shares a pad with a GPIO such as `PH3` or `PA14`.
+    pub fn boot(
+        &self,
+        packages: &[stm32_data_serde::chip::Package],
+        group: &ChipGroup,
+        cores: &[stm32_data_serde::chip::Core],
+    ) -> Option<Boot> {
+        let boot_pin = |signal: &str| {
+            let dedicated = packages
+                .iter()
+                .flat_map(|p| &p.pins)
+                .any(|p| p.signals.iter().any(|s| s == signal));
+            if dedicated {
+                return Some(signal.to_string());
+            }
+
+            // Shared pads are named like `PH3-BOOT0`.
+            let mut shared: Vec<&String> = group
+                .pins
+                .iter()
+                .filter(|(_, p)| p.name.split('-').any(|s| s.trim() == signal))
+                .map(|(name, _)| name)
+                .collect();
+            shared.sort();
+            shared.first().map(|s| s.to_string())
+        };
+
+        let flash_version = version_of(cores, "FLASH");
+        let boot = Boot {
+            boot0: boot_pin("BOOT0"),
+            boot1: boot_pin("BOOT1"),
+            option_bits: flash_version
+                .and_then(|v| self.option_bits.get(v))
+                .cloned()
+                .unwrap_or_default(),
+        };
+
+        (boot != Boot::default()).then_some(boot)
+    }
+
+    /// Get the wakeup pins of a chip, with the PWR fields that configure them.
+    pub fn wakeup_pins(&self, chip_name: &str, cores: &[stm32_data_serde::chip::Core]) -> Vec<WakeupPin> {
+        let Some(lines) = version_of(cores, "PWR").and_then(|v| self.wakeup_lines.get(v)) else {
+            return Vec::new();
+        };
+        let exits = self.wakeup_modes.get(chip_name).next().cloned().unwrap();
+
+        let mut pins: HashMap<u8, Vec<String>> = HashMap::new();
+        for p in cores.iter().flat_map(|c| &c.peripherals).filter(|p| p.name == "PWR") {
+            for pin in &p.pins {
+                // WBA names the pins of a line `WKUP1_0`, `WKUP1_1`, ...
+                let Some(m) = regex!(r"^WKUP(\d+)(_\d+)?$").captures(&pin.signal) else {
+                    continue;
+                };
+                pins.entry(m[1].parse().unwrap()).or_default().push(pin.pin.clone());
+            }
+        }
+
+        let mut res: Vec<WakeupPin> = pins
+            .into_iter()
+            .filter_map(|(line, mut pins)| {
+                let l = lines.get(&line)?;
+                pins.sort();
+                pins.dedup();
+                Some(WakeupPin {
+                    line,
+                    pins,
+                    enable: l.enable.clone()?,
+                    polarity: l.polarity.clone(),
+                    exits: exits.clone(),
+                })
+            })
+            .collect();
+        res.sort_by_key(|p| p.line);
+        res
+    }
+}
+
+fn version_of<'a>(cores: &'a [stm32_data_serde::chip::Core], peri_name: &str) -> Option<&'a str> {
+    cores
+        .iter()
+        .flat_map(|c| &c.peripherals)
+        .find(|p| p.name == peri_name)
+        .and_then(|p| p.registers.as_ref())
+        .map(|r| r.version.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boot_fields() {
+        let registers = Registers::load(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/registers")).unwrap();
+        let boot = ParsedBoot::parse(&registers).unwrap();
+
+        let option_bits = |version: &str| -> Vec<(&str, &str)> {
+            boot.option_bits[version]
+                .iter()
+                .map(|b| (b.register.as_str(), b.field.as_str()))
+                .collect()
+        };
+        assert_eq!(
+            option_bits("g0x1"),
+            [("OPTR", "nBOOT0"), ("OPTR", "nBOOT1"), ("OPTR", "nBOOT_SEL")]
+        );
+        assert_eq!(
+            option_bits("l4"),
+            [("OPTR", "nBOOT0"), ("OPTR", "nBOOT1"), ("OPTR", "nSWBOOT0")]
+        );
+
+        let field = |register: &str, field: &str, index: Option<u32>| wakeup_pin::Field {
+            register: register.to_string(),
+            field: field.to_string(),
+            index,
+        };
+
+        // L4 has an `EWUP` array and one polarity bit per line
+        let l4 = &boot.wakeup_lines["l4"];
+        assert_eq!(l4.len(), 5);
+        assert_eq!(l4[&3].enable, Some(field("CR3", "EWUP", Some(2))));
+        assert_eq!(l4[&3].polarity, Some(field("CR4", "WP3", None)));
+
+        // F4 has `EWUP` for the first line and `EWUP2` for the second, and no polarity
+        let f4 = &boot.wakeup_lines["f4"];
+        assert_eq!(f4[&1].enable, Some(field("CSR1", "EWUP", None)));
+        assert_eq!(f4[&2].enable, Some(field("CSR1", "EWUP2", None)));
+        assert_eq!(f4[&2].polarity, None);
+
+        let exits = |chip: &str| boot.wakeup_modes.get(chip).next().cloned();
+        assert_eq!(exits("STM32L476RG"), Some(LowPowerMode::Shutdown));
+        assert_eq!(exits("STM32WB55RG"), Some(LowPowerMode::Shutdown));
+        assert_eq!(exits("STM32WBA52CG"), Some(LowPowerMode::Standby));
+        assert_eq!(exits("STM32F429ZI"), Some(LowPowerMode::Standby));
+    }
+}
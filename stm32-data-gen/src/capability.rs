@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use chiptool::ir::{Array, BlockItem, BlockItemInner, Field, IR, Register};
+use lazy_regex::regex;
+use stm32_data_serde::chip::core::peripheral::{Capabilities, Provenance, capabilities};
+
+use crate::registers::Registers;
+use crate::util::{RuleSource, new_regex_map};
+
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Capability {
+    CounterBits,
+    Channels,
+    ComplementaryChannels,
+    Resolutions,
+    #[serde(untagged)]
+    Feature(capabilities::Feature),
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RegisterRule {
+    kind: String,
+    /// Regex on the register blocks to apply to, e.g. `U?S?ART` to leave out `LPUART`.
+    #[serde(default)]
+    block: Option<String>,
+    #[serde(default)]
+    register: Option<String>,
+    #[serde(default)]
+    field: Option<String>,
+    capability: Capability,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InstanceRule {
+    /// Regex on `chip:peripheral`
+    #[serde(rename = "match")]
+    pattern: String,
+    source: RuleSource,
+    fifo_depth: u8,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Rules {
+    registers: Vec<RegisterRule>,
+    #[serde(default)]
+    instances: Vec<InstanceRule>,
+}
+
+pub struct ParsedCapabilities {
+    /// Register file -> block -> capabilities read from the block
+    blocks: HashMap<String, HashMap<String, Capabilities>>,
+    instances: regex_map::RegexMap<InstanceRule>,
+}
+
+impl ParsedCapabilities {
+    /// Apply the rules in `data/capabilities.yaml` to every register block in `data/registers`.
+    pub fn parse(registers: &Registers) -> anyhow::Result<Self> {
+        Self::load("data/capabilities.yaml", registers)
+    }
+
+    fn load(path: &str, registers: &Registers) -> anyhow::Result<Self> {
+        let rules: Rules = serde_yaml::from_str(&std::fs::read_to_string(path).context(path.to_string())?)
+            .context(path.to_string())?;
+
+        let mut blocks: HashMap<String, HashMap<String, Capabilities>> = HashMap::new();
+        let mut used = vec![false; rules.registers.len()];
+        let block_filters = rules
+            .registers
+            .iter()
+            .map(|r| {
+                r.block
+                    .as_ref()
+                    .map(|b| regex::Regex::new(&format!("^(?:{b})$")).with_context(|| format!("{path}: {b}")))
+                    .transpose()
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        for (name, ir) in &registers.registers {
+            for block in ir.blocks.keys() {
+                let mut caps = Capabilities::default();
+                for ((rule, filter), used) in rules.registers.iter().zip(&block_filters).zip(&mut used) {
+                    if !name.starts_with(&format!("{}_", rule.kind)) {
+                        continue;
+                    }
+                    if filter.as_ref().is_some_and(|f| !f.is_match(block)) {
+                        continue;
+                    }
+                    if apply(ir, block, rule, &mut caps).with_context(|| format!("{path}: {name} {block}"))? {
+                        *used = true;
+                    }
+                }
+                if caps != Capabilities::default() {
+                    caps.resolutions.sort_by(|a, b| b.cmp(a));
+                    caps.resolutions.dedup();
+                    caps.features.sort();
+                    caps.features.dedup();
+                    blocks.entry(name.clone()).or_default().insert(block.clone(), caps);
+                }
+            }
+        }
+
+        if let Some(i) = used.iter().position(|used| !used) {
+            let rule = &rules.registers[i];
+            anyhow::bail!(
+                "{}: rule for {:?} on {} {}.{} matches no register block",
+                path,
+                rule.capability,
+                rule.kind,
+                rule.register.as_deref().unwrap_or("*"),
+                rule.field.as_deref().unwrap_or("*"),
+            );
+        }
+
+        for rule in &rules.instances {
+            regex::Regex::new(&rule.pattern).with_context(|| format!("{}: {}", path, rule.pattern))?;
+            rule.source
+                .validate()
+                .with_context(|| format!("{}: {}", path, rule.pattern))?;
+        }
+        RuleSource::check_unverified(path, rules.instances.iter().map(|r| &r.source))?;
+
+        Ok(Self {
+            blocks,
+            instances: new_regex_map(rules.instances.into_iter().map(|r| (r.pattern.clone(), r))),
+        })
+    }
+
+    /// Get the capabilities of a peripheral instance, and where the ones that are not from its
+    /// register block come from.
+    pub fn get(
+        &self,
+        chip_name: &str,
+        peripheral: &stm32_data_serde::chip::core::Peripheral,
+    ) -> (Option<Capabilities>, Option<Provenance>) {
+        let mut caps = peripheral
+            .registers
+            .as_ref()
+            .and_then(|r| self.blocks.get(&format!("{}_{}", r.kind, r.version))?.get(&r.block))
+            .cloned()
+            .unwrap_or_default();
+
+        let rule = self.instances.get(&format!("{}:{}", chip_name, peripheral.name)).next();
+        if let Some(rule) = rule {
+            caps.fifo_depth = Some(rule.fifo_depth);
+        }
+
+        let caps = (caps != Capabilities::default()).then_some(caps);
+        (caps, rule.map(|r| r.source.provenance("capabilities")))
+    }
+}
+
+/// Apply a rule to a register block. Returns whether the block has the register and field the
+/// rule is about.
+fn apply(ir: &IR, block: &str, rule: &RegisterRule, caps: &mut Capabilities) -> anyhow::Result<bool> {
+    let items: Vec<&BlockItem> = match &rule.register {
+        Some(register) => find_register(ir, block, register).into_iter().collect(),
+        None => block_registers(ir, block),
+    };
+
+    let found = items.iter().find_map(|item| {
+        let BlockItemInner::Register(reg) = &item.inner else {
+            return None;
+        };
+        match &rule.field {
+            None => Some((*item, reg, None)),
+            Some(field) => {
+                let field = find_field(ir, reg.fieldset.as_deref()?, field)?;
+                Some((*item, reg, Some(field)))
+            }
+        }
+    });
+
+    // `CNT` of 32-bit timers has no fields, the counter is the whole register.
+    let found = match (found, rule.capability) {
+        (None, Capability::CounterBits) => items.iter().find_map(|item| match &item.inner {
+            BlockItemInner::Register(reg @ Register { fieldset: None, .. }) => Some((*item, reg, None)),
+            _ => None,
+        }),
+        (found, _) => found,
+    };
+
+    let Some((item, reg, field)) = found else {
+        return Ok(false);
+    };
+
+    match rule.capability {
+        Capability::CounterBits => {
+            let bits = field.map_or(reg.bit_size, |f| f.bit_size);
+            caps.counter_bits = Some(bits.try_into()?);
+        }
+        Capability::Channels => caps.channels = Some(array_len(item.array.as_ref()).try_into()?),
+        Capability::ComplementaryChannels => {
+            let Some(field) = field else {
+                anyhow::bail!("complementary_channels needs a field");
+            };
+            caps.complementary_channels = Some(array_len(field.array.as_ref()).try_into()?);
+        }
+        Capability::Resolutions => {
+            let Some(enumm) = field.and_then(|f| f.enumm.as_ref()) else {
+                anyhow::bail!("resolutions needs a field with an enum");
+            };
+            let enumm = ir.enums.get(enumm).with_context(|| format!("missing enum {enumm}"))?;
+            for variant in &enumm.variants {
+                if let Some(m) = regex!(r"^Bits(\d+)$").captures(&variant.name) {
+                    caps.resolutions.push(m[1].parse()?);
+                }
+            }
+        }
+        Capability::Feature(feature) => caps.features.push(feature),
+    }
+
+    Ok(true)
+}
+
+/// Find a register of a block, or of the blocks it extends.
+fn find_register<'a>(ir: &'a IR, block: &str, name: &str) -> Option<&'a BlockItem> {
+    let b = ir.blocks.get(block)?;
+    b.items
+        .iter()
+        .find(|i| i.name == name && matches!(i.inner, BlockItemInner::Register(_)))
+        .or_else(|| find_register(ir, b.extends.as_deref()?, name))
+}
+
+/// All registers of a block, including the ones of the blocks it extends that it doesn't redefine.
+fn block_registers<'a>(ir: &'a IR, block: &str) -> Vec<&'a BlockItem> {
+    let Some(b) = ir.blocks.get(block) else {
+        return Vec::new();
+    };
+    let mut items: Vec<&BlockItem> = b
+        .items
+        .iter()
+        .filter(|i| matches!(i.inner, BlockItemInner::Register(_)))
+        .collect();
+    if let Some(base) = b.extends.as_deref() {
+        for item in block_registers(ir, base) {
+            if !items.iter().any(|i| i.name == item.name) {
+                items.push(item);
+            }
+        }
+    }
+    items
+}
+
+/// Find a field of a fieldset, or of the fieldsets it extends.
+fn find_field<'a>(ir: &'a IR, fieldset: &str, name: &str) -> Option<&'a Field> {
+    let fs = ir.fieldsets.get(fieldset)?;
+    fs.fields
+        .iter()
+        .find(|f| f.name == name)
+        .or_else(|| find_field(ir, fs.extends.as_deref()?, name))
+}
+
+fn array_len(array: Option<&Array>) -> u32 {
+    match array {
+        None => 1,
+        Some(Array::Regular(a)) => a.len,
+        Some(Array::Cursed(a)) => a.offsets.len() as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use capabilities::Feature;
+
+    use super::*;
+
+    #[test]
+    fn test_capabilities() {
+        let registers = Registers::load(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/registers")).unwrap();
+        let caps = ParsedCapabilities::load(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../data/capabilities.yaml"),
+            &registers,
+        )
+        .unwrap();
+        let block = |file: &str, block: &str| &caps.blocks[file][block];
+
+        // The 32-bit counter of TIM2 is the whole CNT register
+        let tim2 = block("timer_v2", "TIM_GP32");
+        assert_eq!((tim2.counter_bits, tim2.channels), (Some(32), Some(4)));
+        assert_eq!(tim2.complementary_channels, None);
+
+        let tim1 = block("timer_v2", "TIM_ADV");
+        assert_eq!((tim1.counter_bits, tim1.channels), (Some(16), Some(4)));
+        assert_eq!(tim1.complementary_channels, Some(4));
+        assert!(tim1.features.contains(&Feature::Break));
+
+        // LPUART has the USART fields but no LIN, smartcard, IrDA or auto baud rate
+        let usart = block("usart_v4", "USART");
+        let lpuart = block("usart_v4", "LPUART");
+        for feature in [Feature::Lin, Feature::Smartcard, Feature::Irda, Feature::AutoBaudRate] {
+            assert!(usart.features.contains(&feature), "{feature:?}");
+            assert!(!lpuart.features.contains(&feature), "{feature:?}");
+        }
+        assert!(lpuart.features.contains(&Feature::Fifo));
+        assert!(lpuart.features.contains(&Feature::DriverEnable));
+
+        assert_eq!(block("adc_g4", "ADC").resolutions, [12, 10, 8, 6]);
+        assert!(block("spi_v3_i2s", "SPI").features.contains(&Feature::I2s));
+
+        // The FIFO depth of an instance comes from a rule with a source
+        let fifo_depth = |key: &str| caps.instances.get(key).next().map(|r| r.fifo_depth);
+        assert_eq!(fifo_depth("STM32H743ZI:SPI1"), Some(16));
+        assert_eq!(fifo_depth("STM32H743ZI:SPI4"), Some(8));
+        assert_eq!(fifo_depth("STM32H743ZI:SPI10"), None);
+        let rule = caps.instances.get("STM32H743ZI:SPI1").next().unwrap();
+        assert_eq!(rule.source.provenance("capabilities").document, "RM0433, RM0399");
+    }
+}
@@ -4,6 +4,25 @@ use std::hash::Hash;
 use stm32_data_serde::Chip;
 
 pub fn check(chip: &Chip) {
+    for flash in &chip.flash {
+        for bank in &flash.banks {
+            // Sectors must tile the bank without gaps.
+            let mut offset = 0;
+            for sector in &bank.sectors {
+                if sector.offset != offset {
+                    panic!("{}: gap before sector {} of {}", chip.name, sector.index, bank.name);
+                }
+                offset += sector.size;
+            }
+            if offset != bank.size {
+                panic!(
+                    "{}: sectors of {} cover {:#x} of {:#x}",
+                    chip.name, bank.name, offset, bank.size
+                );
+            }
+        }
+    }
+
     for core in &chip.cores {
         let peris = mapify(&core.peripherals, |p| &p.name);
         // Each channel should have its own interrupt signal, except for MDMA, which uses one GLOBAL interrupt
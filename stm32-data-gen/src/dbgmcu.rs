@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use lazy_regex::regex;
+use stm32_data_serde::chip::core::peripheral::DebugFreeze;
+
+use crate::registers::Registers;
+
+#[derive(Debug)]
+pub struct ParsedDbgmcus {
+    /// DBGMCU version -> peripheral name -> freeze bit
+    dbgmcus: HashMap<String, HashMap<String, DebugFreeze>>,
+}
+
+impl ParsedDbgmcus {
+    /// Parse the peripheral freeze bits from the `dbgmcu_xx` yaml files in `data/registers`
+    pub fn parse(registers: &Registers) -> anyhow::Result<Self> {
+        let mut dbgmcus = HashMap::new();
+
+        for (name, ir) in &registers.registers {
+            let Some(version) = name.strip_prefix("dbgmcu_") else {
+                continue;
+            };
+
+            let mut freeze: HashMap<String, DebugFreeze> = HashMap::new();
+            for (reg, body) in &ir.fieldsets {
+                for field in &body.fields {
+                    // Freeze bits are named `TIM1`, `DBG_TIM1_STOP`, `TIM1_STOP` or, for I2C, `DBG_I2C1_SMBUS_TIMEOUT`.
+                    // Other fields, like `DBG_STOP` or `DBG_SLEEP`, configure the debug support itself.
+                    let name = field.name.strip_prefix("DBG_").unwrap_or(&field.name);
+                    let (peri, is_stop) = if let Some(m) =
+                        regex!(r"^([A-Z0-9_]+?)(_STOP|_SMBUS_TIMEOUT)$").captures(name)
+                    {
+                        (m.get(1).unwrap().as_str(), &m[2] == "_STOP")
+                    } else if regex!(r"^(LPTIMER|(TIM|LPTIM|HRTIM|I2C|CAN|FDCAN|IWDG|WWDG|RTC)\d*)$").is_match(name) {
+                        (name, true)
+                    } else {
+                        continue;
+                    };
+
+                    let val = DebugFreeze {
+                        register: reg.clone(),
+                        field: field.name.clone(),
+                    };
+
+                    // Some chips have both a stop and an SMBUS timeout bit for I2C, prefer the stop bit.
+                    match freeze.get(peri) {
+                        Some(_) if !is_stop => {}
+                        _ => {
+                            freeze.insert(peri.to_string(), val);
+                        }
+                    }
+                }
+            }
+
+            dbgmcus.insert(version.to_string(), freeze);
+        }
+
+        Ok(Self { dbgmcus })
+    }
+
+    pub fn match_peri_freeze(&self, dbgmcu_version: &str, peri_name: &str) -> Option<DebugFreeze> {
+        const FALLBACKS: &[(&str, &[&str])] = &[
+            ("CAN1", &["CAN"]),
+            ("FDCAN1", &["FDCAN"]),
+            ("FDCAN2", &["FDCAN"]),
+            ("FDCAN3", &["FDCAN"]),
+            ("FMPI2C1", &["I2CFMP"]),
+            ("LPTIM1", &["LPTIM", "LPTIMER"]),
+            ("GPDMA1", &["GPDMA"]),
+            ("LPDMA1", &["LPDMA"]),
+            ("IWDG", &["IWDG1"]),
+            ("WWDG", &["WWDG1"]),
+        ];
+
+        let freeze = self.dbgmcus.get(dbgmcu_version)?;
+
+        if let Some(res) = freeze.get(peri_name) {
+            return Some(res.clone());
+        }
+
+        // Unlike RCC, don't fall back from `LPTIM2` to `LPTIM`: a single `LPTIM` bit only freezes LPTIM1.
+        let (_, rename) = FALLBACKS.iter().find(|(n, _)| *n == peri_name)?;
+        rename.iter().find_map(|n| freeze.get(*n)).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_peri_freeze() {
+        let registers = Registers::load(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/registers")).unwrap();
+        let dbgmcus = ParsedDbgmcus::parse(&registers).unwrap();
+        let field = |version: &str, peri: &str| dbgmcus.match_peri_freeze(version, peri).map(|f| f.field);
+
+        assert_eq!(field("f1", "TIM1").as_deref(), Some("TIM1"));
+        assert_eq!(field("f4", "TIM1").as_deref(), Some("TIM1"));
+        assert_eq!(field("g4", "LPTIM1").as_deref(), Some("LPTIMER"));
+        assert_eq!(field("g4", "LPTIM2"), None);
+
+        // F1 only has the SMBUS timeout bit for I2C, F4 names it without the `DBG_` prefix
+        assert_eq!(field("f1", "I2C1").as_deref(), Some("DBG_I2C1_SMBUS_TIMEOUT"));
+        assert_eq!(field("f4", "I2C1").as_deref(), Some("I2C1_SMBUS_TIMEOUT"));
+
+        // The debug configuration bits of DBGMCU_CR are not peripherals
+        for (version, freeze) in &dbgmcus.dbgmcus {
+            for peri in ["DBG", "STOP", "SLEEP", "STANDBY", "DBG_SLEEP", "DBG_STANDBY"] {
+                assert!(!freeze.contains_key(peri), "{version}: {peri}");
+            }
+        }
+    }
+}
@@ -1,13 +1,14 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use anyhow::Context;
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
-use stm32_data_serde::chip::core::peripheral::RemapInfo;
+use serde::Deserialize;
+use stm32_data_serde::chip::core::peripheral::{DmaTrigger, DmamuxInput, RemapInfo};
 
 use crate::normalize_peris::normalize_peri_name;
-use crate::perimap::Perimap;
-use crate::util::HashMapFns;
+use crate::registers::Registers;
+use crate::util::{HashMapFns, RuleSource};
 
 mod xml {
     use serde::Deserialize;
@@ -81,6 +82,10 @@ mod xml {
 pub struct ChipDma {
     pub peripherals: HashMap<String, Vec<stm32_data_serde::chip::core::peripheral::DmaChannel>>,
     pub channels: Vec<stm32_data_serde::chip::core::DmaChannels>,
+    /// Peripheral -> DMAMUX synchronization and trigger inputs it drives
+    pub dmamux_inputs: HashMap<String, Vec<DmamuxInput>>,
+    /// Peripheral -> GPDMA/HPDMA/LPDMA hardware triggers it drives
+    pub triggers: HashMap<String, Vec<DmaTrigger>>,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -105,214 +110,174 @@ impl RemapKey {
 #[derive(Debug)]
 pub struct DmaChannels(pub HashMap<String, ChipDma>);
 
-fn cfgr(cfgr: u8, field: String, value: u8) -> RemapInfo {
-    RemapInfo {
-        register: format!("CFGR{cfgr}").to_string(),
-        field,
-        value,
-    }
+/// An entry of `data/dma_remaps.yaml`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DmaRemapEntry {
+    peripheral: String,
+    signals: Vec<String>,
+    dma: u8,
+    channel: u8,
+    remap: Vec<RemapInfo>,
 }
 
-fn build_remap_info_f3() -> HashMap<RemapKey, Vec<RemapInfo>> {
-    let mut remap_map = HashMap::new();
-
-    macro_rules! cfgr1_dma1 {
-        ($field:expr, $value:expr, $peripheral:expr, $signal:expr, $channel:expr) => {
-            remap_map
-                .try_insert_stable(
-                    RemapKey::new($peripheral, $signal, 1, $channel),
-                    vec![cfgr(1, $field.to_string(), $value)],
-                )
-                .unwrap();
-        };
-    }
-
-    macro_rules! cfgr1_dma2 {
-        ($field:expr, $value:expr, $peripheral:expr, $signal:expr, $channel:expr) => {
-            remap_map
-                .try_insert_stable(
-                    RemapKey::new($peripheral, $signal, 2, $channel),
-                    vec![cfgr(1, $field.to_string(), $value)],
-                )
-                .unwrap();
-        };
-    }
-
-    macro_rules! cfgr3_dma1 {
-        ($field:expr, $value:expr, $peripheral:expr, $signal:expr, $channel:expr) => {
-            remap_map
-                .try_insert_stable(
-                    RemapKey::new($peripheral, $signal, 1, $channel),
-                    vec![cfgr(3, $field.to_string(), $value)],
-                )
-                .unwrap();
-        };
-    }
-
-    cfgr1_dma1!("DAC2_CH1_DMA_RMP", 1, "DAC2", "CH1", 5); // This bit must always be set. Therefore, no entry with "0".
-    cfgr1_dma2!("TIM18_DAC2_OUT1_DMA_RMP", 0, "TIM18", "UP", 5);
-    cfgr1_dma2!("TIM18_DAC2_OUT1_DMA_RMP", 0, "DAC2", "CH1", 5);
-    cfgr1_dma1!("TIM18_DAC2_OUT1_DMA_RMP", 1, "TIM18", "UP", 4);
-    cfgr1_dma1!("TIM18_DAC2_OUT1_DMA_RMP", 1, "DAC2", "CH1", 4);
-
-    cfgr1_dma2!("TIM7_DAC1_CH2_DMA_RMP", 0, "TIM7", "UP", 4);
-    cfgr1_dma2!("TIM7_DAC1_CH2_DMA_RMP", 0, "DAC1", "CH2", 4);
-    cfgr1_dma2!("TIM7_DAC1_CH2_DMA_RMP", 0, "DAC", "CH2", 4);
-    cfgr1_dma1!("TIM7_DAC1_CH2_DMA_RMP", 1, "TIM7", "UP", 4);
-    cfgr1_dma1!("TIM7_DAC1_CH2_DMA_RMP", 1, "DAC1", "CH2", 4);
-    cfgr1_dma1!("TIM7_DAC1_CH2_DMA_RMP", 1, "DAC", "CH2", 4);
-
-    // RM0313 (STM32F37xxx) talks inconsistently about TIM6 and TIM7 in the field description.
-    // Figure 22+23. "DMA[12] request mapping" have footnotes stating the remap bit must be set/cleared, but the figure itself has not any footnote annotations inside.
-    // Assuming the documentation is bad and STM32F37xxx behaves like any other F3 having this field.
-    cfgr1_dma2!("TIM6_DAC1_CH1_DMA_RMP", 0, "TIM6", "UP", 3);
-    cfgr1_dma2!("TIM6_DAC1_CH1_DMA_RMP", 0, "DAC1", "CH1", 3);
-    cfgr1_dma2!("TIM6_DAC1_CH1_DMA_RMP", 0, "DAC", "CH1", 3);
-    cfgr1_dma1!("TIM6_DAC1_CH1_DMA_RMP", 1, "TIM6", "UP", 3);
-    cfgr1_dma1!("TIM6_DAC1_CH1_DMA_RMP", 1, "DAC1", "CH1", 3);
-    cfgr1_dma1!("TIM6_DAC1_CH1_DMA_RMP", 1, "DAC", "CH1", 3);
-
-    cfgr1_dma1!("TIM17_DMA_RMP", 0, "TIM17", "CH1", 1);
-    cfgr1_dma1!("TIM17_DMA_RMP", 0, "TIM17", "UP", 1);
-    cfgr1_dma1!("TIM17_DMA_RMP", 1, "TIM17", "CH1", 7);
-    cfgr1_dma1!("TIM17_DMA_RMP", 1, "TIM17", "UP", 7);
-
-    cfgr1_dma1!("TIM16_DMA_RMP", 0, "TIM16", "CH1", 3);
-    cfgr1_dma1!("TIM16_DMA_RMP", 0, "TIM16", "UP", 3);
-    cfgr1_dma1!("TIM16_DMA_RMP", 1, "TIM16", "CH1", 6);
-    cfgr1_dma1!("TIM16_DMA_RMP", 1, "TIM16", "UP", 6);
-
-    cfgr1_dma2!("ADC2_DMA_RMP", 0, "ADC2", "ADC2", 1);
-    cfgr1_dma2!("ADC2_DMA_RMP", 0, "ADC4", "ADC4", 2);
-    cfgr1_dma2!("ADC2_DMA_RMP", 1, "ADC2", "ADC2", 3);
-    cfgr1_dma2!("ADC2_DMA_RMP", 1, "ADC4", "ADC4", 4);
-
-    // RM0316 (STM32F303xB/C/D/E, STM32F358xC, STM32F398xE, STM32F303x6/8, STM32F328x8) states "ADC2 mapped on DMA2" if SYSCFG_CFGR3.ADC_DMA_RMP[1] is cleared,
-    // but CFGR3 does only exist on devices not having DMA2 (STM32F303x6/x8, STM32F328, STM334xxx)!
-    // Its reset value is 1. So, we write this bit always as 1.
-    // RM0364 (STM334xxx) states "reserved". So, this seems to be an error in RM0316.
-    cfgr3_dma1!("ADC2_DMA_RMP", 0b10, "ADC2", "ADC2", 2);
-    cfgr3_dma1!("ADC2_DMA_RMP", 0b11, "ADC2", "ADC2", 4);
-
-    // The following definitions omit 0b11, because 0b11 maps to the same channels as 0b00.
-    cfgr3_dma1!("I2C1_TX_DMA_RMP", 0b00, "I2C1", "TX", 6);
-    cfgr3_dma1!("I2C1_TX_DMA_RMP", 0b01, "I2C1", "TX", 2);
-    cfgr3_dma1!("I2C1_TX_DMA_RMP", 0b10, "I2C1", "TX", 4);
-
-    cfgr3_dma1!("I2C1_RX_DMA_RMP", 0b00, "I2C1", "RX", 7);
-    cfgr3_dma1!("I2C1_RX_DMA_RMP", 0b01, "I2C1", "RX", 3);
-    cfgr3_dma1!("I2C1_RX_DMA_RMP", 0b10, "I2C1", "RX", 5);
-
-    cfgr3_dma1!("SPI1_TX_DMA_RMP", 0b00, "SPI1", "TX", 3);
-    cfgr3_dma1!("SPI1_TX_DMA_RMP", 0b01, "SPI1", "TX", 5);
-    cfgr3_dma1!("SPI1_TX_DMA_RMP", 0b10, "SPI1", "TX", 7);
-
-    cfgr3_dma1!("SPI1_RX_DMA_RMP", 0b00, "SPI1", "RX", 2);
-    cfgr3_dma1!("SPI1_RX_DMA_RMP", 0b01, "SPI1", "RX", 4);
-    cfgr3_dma1!("SPI1_RX_DMA_RMP", 0b10, "SPI1", "RX", 6);
-
-    remap_map
+/// A rule of `data/dma_remaps.yaml`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DmaRemapRule {
+    /// Regex on the DMA IP version
+    #[serde(rename = "match")]
+    pattern: String,
+    remaps: Vec<DmaRemapEntry>,
 }
 
-fn build_remap_info_f0_bdma_v1() -> HashMap<RemapKey, Vec<RemapInfo>> {
-    let mut remap_map = HashMap::new();
-
-    macro_rules! cfgr1 {
-        ($field:expr, $value:expr, $peripheral:expr, $signal:expr, $channel:expr) => {
-            remap_map
-                .try_insert_stable(
-                    RemapKey::new($peripheral, $signal, 1, $channel),
-                    vec![cfgr(1, $field.to_string(), $value)],
-                )
-                .unwrap();
-        };
-    }
+/// SYSCFG remaps of DMA requests, by DMA IP version.
+struct DmaRemaps(Vec<(regex::Regex, HashMap<RemapKey, Vec<RemapInfo>>)>);
 
-    macro_rules! cfgr1_rmp2 {
-        ($value_rmp:expr, $value_rmp2:expr, $peripheral:expr, $signal:expr, $channel:expr) => {
-            remap_map
-                .try_insert_stable(
-                    RemapKey::new($peripheral, $signal, 1, $channel),
-                    vec![
-                        cfgr(1, format!("{}_DMA_RMP", $peripheral), $value_rmp),
-                        cfgr(1, format!("{}_DMA_RMP2", $peripheral), $value_rmp2),
-                    ],
-                )
-                .unwrap();
-        };
+impl DmaRemaps {
+    /// Load the DMA request remaps from `data/dma_remaps.yaml`, and check that every remap field
+    /// exists in the `syscfg_xx` yaml files in `data/registers` and can hold its value.
+    fn parse(registers: &Registers) -> anyhow::Result<Self> {
+        Self::load("data/dma_remaps.yaml", registers)
     }
 
-    cfgr1!("TIM3_DMA_RMP", 0, "TIM3", "CH1", 4);
-    cfgr1!("TIM3_DMA_RMP", 0, "TIM3", "TRIG", 4);
-    cfgr1!("TIM3_DMA_RMP", 1, "TIM3", "CH1", 6);
-    cfgr1!("TIM3_DMA_RMP", 1, "TIM3", "TRIG", 6);
-
-    cfgr1!("TIM2_DMA_RMP", 0, "TIM2", "CH2", 3);
-    cfgr1!("TIM2_DMA_RMP", 0, "TIM2", "CH4", 4);
-    cfgr1!("TIM2_DMA_RMP", 1, "TIM2", "CH2", 7);
-    cfgr1!("TIM2_DMA_RMP", 1, "TIM2", "CH4", 7);
-
-    cfgr1!("TIM1_DMA_RMP", 0, "TIM1", "CH1", 2);
-    cfgr1!("TIM1_DMA_RMP", 0, "TIM1", "CH2", 3);
-    cfgr1!("TIM1_DMA_RMP", 0, "TIM1", "CH3", 4);
-    cfgr1!("TIM1_DMA_RMP", 1, "TIM1", "CH1", 6);
-    cfgr1!("TIM1_DMA_RMP", 1, "TIM1", "CH2", 6);
-    cfgr1!("TIM1_DMA_RMP", 1, "TIM1", "CH3", 6);
-
-    cfgr1!("I2C1_DMA_RMP", 0, "I2C1", "RX", 3);
-    cfgr1!("I2C1_DMA_RMP", 0, "I2C1", "TX", 2);
-    cfgr1!("I2C1_DMA_RMP", 1, "I2C1", "RX", 7);
-    cfgr1!("I2C1_DMA_RMP", 1, "I2C1", "TX", 6);
-
-    cfgr1!("USART3_DMA_RMP", 0, "USART3", "RX", 6);
-    cfgr1!("USART3_DMA_RMP", 0, "USART3", "TX", 7);
-    cfgr1!("USART3_DMA_RMP", 1, "USART3", "RX", 3);
-    cfgr1!("USART3_DMA_RMP", 1, "USART3", "TX", 2);
-
-    cfgr1!("USART2_DMA_RMP", 0, "USART2", "RX", 5);
-    cfgr1!("USART2_DMA_RMP", 0, "USART2", "TX", 4);
-    cfgr1!("USART2_DMA_RMP", 1, "USART2", "RX", 6);
-    cfgr1!("USART2_DMA_RMP", 1, "USART2", "TX", 7);
-
-    cfgr1!("SPI2_DMA_RMP", 0, "SPI2", "RX", 4);
-    cfgr1!("SPI2_DMA_RMP", 0, "SPI2", "TX", 5);
-    cfgr1!("SPI2_DMA_RMP", 1, "SPI2", "RX", 6);
-    cfgr1!("SPI2_DMA_RMP", 1, "SPI2", "TX", 7);
-
-    cfgr1_rmp2!(0, 1, "TIM17", "CH1", 7);
-    cfgr1_rmp2!(0, 1, "TIM17", "UP", 7);
-
-    cfgr1_rmp2!(0, 1, "TIM16", "CH1", 6);
-    cfgr1_rmp2!(0, 1, "TIM16", "UP", 6);
-
-    cfgr1_rmp2!(0, 0, "TIM17", "CH1", 1);
-    cfgr1_rmp2!(0, 0, "TIM17", "UP", 1);
-
-    cfgr1_rmp2!(1, 0, "TIM17", "CH1", 2);
-    cfgr1_rmp2!(1, 0, "TIM17", "UP", 2);
+    fn load(path: &str, registers: &Registers) -> anyhow::Result<Self> {
+        let rules: Vec<DmaRemapRule> = serde_yaml::from_str(&std::fs::read_to_string(path).context(path.to_string())?)
+            .context(path.to_string())?;
+
+        let mut res = Vec::new();
+        for rule in rules {
+            let re = regex::Regex::new(&format!("^{}$", rule.pattern))
+                .with_context(|| format!("{path}: {}", rule.pattern))?;
+
+            let mut remap_map = HashMap::new();
+            for entry in rule.remaps {
+                for info in &entry.remap {
+                    let Some(bit_size) = registers.field_bit_size("syscfg", &info.register, &info.field) else {
+                        anyhow::bail!(
+                            "{}: {}: {}.{} not found in the SYSCFG register files",
+                            path,
+                            rule.pattern,
+                            info.register,
+                            info.field
+                        );
+                    };
+                    if u32::from(info.value) >> bit_size != 0 {
+                        anyhow::bail!(
+                            "{}: {}: {}.{} cannot hold {}",
+                            path,
+                            rule.pattern,
+                            info.register,
+                            info.field,
+                            info.value
+                        );
+                    }
+                }
 
-    cfgr1_rmp2!(0, 0, "TIM16", "CH1", 3);
-    cfgr1_rmp2!(0, 0, "TIM16", "UP", 3);
+                for signal in &entry.signals {
+                    if remap_map
+                        .try_insert_stable(
+                            RemapKey::new(&entry.peripheral, signal, entry.dma, entry.channel),
+                            entry.remap.clone(),
+                        )
+                        .is_err()
+                    {
+                        anyhow::bail!(
+                            "{}: {}: duplicate entry for {}_{} on DMA{}_CH{}",
+                            path,
+                            rule.pattern,
+                            entry.peripheral,
+                            signal,
+                            entry.dma,
+                            entry.channel
+                        );
+                    }
+                }
+            }
+            res.push((re, remap_map));
+        }
 
-    cfgr1_rmp2!(1, 0, "TIM16", "CH1", 4);
-    cfgr1_rmp2!(1, 0, "TIM16", "UP", 4);
+        Ok(Self(res))
+    }
 
-    cfgr1!("USART1_RX_DMA_RMP", 0, "USART1", "RX", 3);
-    cfgr1!("USART1_RX_DMA_RMP", 1, "USART1", "RX", 5);
+    /// Get the remap for a DMA request. The first matching rule with an entry for it wins.
+    fn get(&self, version: &str, key: &RemapKey) -> Vec<RemapInfo> {
+        self.0
+            .iter()
+            .filter(|(re, _)| re.is_match(version))
+            .find_map(|(_, remap_map)| remap_map.get(key))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
 
-    cfgr1!("USART1_TX_DMA_RMP", 0, "USART1", "TX", 2);
-    cfgr1!("USART1_TX_DMA_RMP", 1, "USART1", "TX", 4);
+/// DMA register versions that select the request of a channel, with `DMA_CSELR` (bdma_v2) or
+/// `DMA_SxCR.CHSEL` (dma_v2).
+const REQUEST_SELECT_VERSIONS: &[(&str, &str)] = &[("bdma", "v2"), ("dma", "v2")];
+
+/// Check that every DMA request of a controller without DMAMUX can be routed to a channel: with request
+/// selection each channel needs a request number, otherwise a signal that is available on several
+/// channels needs a remap for each of them. Whether a controller selects requests is decided by its
+/// register version, not by the request numbers CubeMX happens to list.
+pub fn check_channel_assignments(
+    chip_name: &str,
+    peripherals: &[stm32_data_serde::chip::core::Peripheral],
+) -> anyhow::Result<()> {
+    for dma in peripherals {
+        let Some(registers) = &dma.registers else {
+            continue;
+        };
+        if registers.kind != "dma" && registers.kind != "bdma" {
+            continue;
+        }
+        let request_select = REQUEST_SELECT_VERSIONS.contains(&(registers.kind.as_str(), registers.version.as_str()));
+
+        let prefix = format!("{}_CH", dma.name);
+        let mut channels: HashMap<(&str, &str), Vec<&stm32_data_serde::chip::core::peripheral::DmaChannel>> =
+            HashMap::new();
+        for p in peripherals {
+            for entry in &p.dma_channels {
+                if entry.channel.as_ref().is_some_and(|ch| ch.starts_with(&prefix)) {
+                    channels.entry((&p.name, &entry.signal)).or_default().push(entry);
+                }
+            }
+        }
 
-    cfgr1!("ADC_DMA_RMP", 0, "ADC", "ADC", 1);
-    cfgr1!("ADC_DMA_RMP", 1, "ADC", "ADC", 2);
+        for ((peri, signal), entries) in channels {
+            for e in &entries {
+                let missing = match (request_select, e.request) {
+                    (true, None) => "request number",
+                    (false, Some(request)) => anyhow::bail!(
+                        "{}: {}_{} on {} has request number {} but {}_{} has no request selection",
+                        chip_name,
+                        peri,
+                        signal,
+                        e.channel.as_ref().unwrap(),
+                        request,
+                        registers.kind,
+                        registers.version
+                    ),
+                    (false, None) if entries.len() > 1 && e.remap.is_empty() => "remap in data/dma_remaps.yaml",
+                    _ => continue,
+                };
+                anyhow::bail!(
+                    "{}: {}_{} on {} has no {}",
+                    chip_name,
+                    peri,
+                    signal,
+                    e.channel.as_ref().unwrap(),
+                    missing
+                );
+            }
+        }
+    }
 
-    remap_map
+    Ok(())
 }
 
 impl DmaChannels {
-    pub fn parse(perimap: &Perimap) -> anyhow::Result<Self> {
-        let f0_bdma_v1_remap = build_remap_info_f0_bdma_v1();
-        let f3_remap = build_remap_info_f3();
+    pub fn parse(registers: &Registers) -> anyhow::Result<Self> {
+        let remaps = DmaRemaps::parse(registers)?;
 
         let files = glob::glob("sources/cubedb/mcu/IP/DMA*Modes.xml")?
             .chain(glob::glob("sources/cubedb/mcu/IP/BDMA*Modes.xml")?);
@@ -340,21 +305,8 @@ impl DmaChannels {
                 let mut chip_dma = ChipDma {
                     peripherals: HashMap::new(),
                     channels: Vec::new(),
-                };
-
-                let remap_map = if ff.starts_with("STM32F3") {
-                    &f3_remap
-                } else {
-                    // apply only to F0 devices having bdma_v1
-                    if let Some((kind, version, _)) = perimap.get(format!("{}:DMA", &ff[..9]).as_str()) {
-                        if ff.starts_with("STM32F0") && *kind == "bdma" && *version == "v1" {
-                            &f0_bdma_v1_remap
-                        } else {
-                            &HashMap::new()
-                        }
-                    } else {
-                        &HashMap::new()
-                    }
+                    dmamux_inputs: HashMap::new(),
+                    triggers: HashMap::new(),
                 };
 
                 for dma in parsed.mode_logic_operator.modes {
@@ -418,6 +370,11 @@ impl DmaChannels {
                             }
                         }
 
+                        let inputs_file = format!("data/dmamux/inputs/{dmamux_file}_{dmamux}.yaml");
+                        for (peri, mut inputs) in load_dmamux_inputs(&inputs_file, dmamux)? {
+                            chip_dma.dmamux_inputs.entry(peri).or_default().append(&mut inputs);
+                        }
+
                         let mut dmamux_channel = 0;
                         for n in dma_peri_name.split(',') {
                             let n = n.trim();
@@ -516,22 +473,27 @@ impl DmaChannels {
                                         "SPDIF" => "SPDIFRX1",
                                         x => x,
                                     };
-                                    for request in target_requests {
-                                        assert!(!request.contains(':'));
+                                    for request_signal in target_requests {
+                                        assert!(!request_signal.contains(':'));
 
                                         let remap_key = RemapKey::new(
                                             target_peri_name,
-                                            request,
+                                            request_signal,
                                             dma_peri_name.chars().last().unwrap().to_digit(10).unwrap() as u8,
                                             channel_name.parse().unwrap(),
                                         );
 
+                                        // Chips with request selection don't need the SYSCFG remaps
+                                        let request = requests.get(&original_target_name).copied();
                                         let entry = stm32_data_serde::chip::core::peripheral::DmaChannel {
-                                            signal: request.to_string(),
+                                            signal: request_signal.to_string(),
                                             channel: Some(format!("{dma_peri_name}_CH{channel_name}")),
                                             dmamux: None,
-                                            remap: remap_map.get(&remap_key).cloned().unwrap_or_default(),
-                                            request: requests.get(&original_target_name).copied(),
+                                            remap: match request {
+                                                Some(_) => vec![],
+                                                None => remaps.get(&ff, &remap_key),
+                                            },
+                                            request,
                                             dma: None,
                                         };
                                         chip_dma
@@ -589,6 +551,8 @@ impl DmaChannels {
             let mut chip_dma = ChipDma {
                 peripherals: HashMap::new(),
                 channels: Vec::new(),
+                dmamux_inputs: HashMap::new(),
+                triggers: HashMap::new(),
             };
 
             for (request_name, request_num) in load_dma_mux(file)? {
@@ -609,6 +573,13 @@ impl DmaChannels {
                     });
             }
 
+            if ["GPDMA", "HPDMA", "LPDMA"]
+                .iter()
+                .any(|kind| instance.starts_with(kind))
+            {
+                chip_dma.triggers = load_dma_triggers(file, instance)?;
+            }
+
             for i in 0..*count {
                 chip_dma.channels.push(stm32_data_serde::chip::core::DmaChannels {
                     name: format!("{instance}_CH{i}"),
@@ -632,3 +603,198 @@ pub fn load_dma_mux(file: &str) -> anyhow::Result<HashMap<String, u8>> {
         "data/dmamux/{file}"
     ))?)?)
 }
+
+/// Load the hardware trigger (`TRIGSEL`) table of a GPDMA, HPDMA or LPDMA from `data/dmamux/triggers`,
+/// grouped by the peripheral driving them. Instances without a table only get the triggers derived from
+/// the cubeprogdb descriptors.
+pub fn load_dma_triggers(file: &str, dma: &str) -> anyhow::Result<HashMap<String, Vec<DmaTrigger>>> {
+    let path = format!("data/dmamux/triggers/{file}");
+    if !std::path::Path::new(&path).exists() {
+        return Ok(HashMap::new());
+    }
+    let triggers: HashMap<String, u8> =
+        serde_yaml::from_str(&std::fs::read_to_string(&path).context(path.clone())?).context(path.clone())?;
+    dma_triggers(&path, dma, &triggers)
+}
+
+/// Group a `PERIPHERAL_SIGNAL: TRIGSEL` table by peripheral, checking each id is used once.
+pub fn dma_triggers(
+    source: &str,
+    dma: &str,
+    triggers: &HashMap<String, u8>,
+) -> anyhow::Result<HashMap<String, Vec<DmaTrigger>>> {
+    let mut seen = HashSet::new();
+    let mut res: HashMap<String, Vec<DmaTrigger>> = HashMap::new();
+    for (name, &trigger) in triggers {
+        if !seen.insert(trigger) {
+            anyhow::bail!("{}: trigger id {} is used more than once", source, trigger);
+        }
+        let Some((peri, signal)) = name.split_once('_') else {
+            anyhow::bail!("{}: {} is not named PERIPHERAL_SIGNAL", source, name);
+        };
+        res.entry(normalize_peri_name(peri).to_string())
+            .or_default()
+            .push(DmaTrigger {
+                dma: dma.to_string(),
+                signal: signal.to_string(),
+                trigger,
+            });
+    }
+    Ok(res)
+}
+
+/// Synchronization (`SYNC_ID`) and trigger (`SIG_ID`) inputs of a DMAMUX, named `PERIPHERAL_SIGNAL`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DmamuxInputs {
+    source: RuleSource,
+    #[serde(default)]
+    sync: HashMap<String, u8>,
+    #[serde(default)]
+    trigger: HashMap<String, u8>,
+}
+
+/// Load the inputs of a DMAMUX from its table in `data/dmamux/inputs`, grouped by the peripheral driving them.
+fn load_dmamux_inputs(path: &str, dmamux: &str) -> anyhow::Result<HashMap<String, Vec<DmamuxInput>>> {
+    let inputs: DmamuxInputs =
+        serde_yaml::from_str(&std::fs::read_to_string(path).context(path.to_string())?).context(path.to_string())?;
+    inputs.source.validate().context(path.to_string())?;
+    RuleSource::check_unverified(path, [&inputs.source])?;
+
+    let mut signals: BTreeMap<&str, DmamuxInput> = BTreeMap::new();
+    for (table, ids, is_sync) in [("sync", &inputs.sync, true), ("trigger", &inputs.trigger, false)] {
+        let mut seen = HashSet::new();
+        for (name, &id) in ids {
+            if !seen.insert(id) {
+                anyhow::bail!("{}: {} id {} is used more than once", path, table, id);
+            }
+            let input = signals.entry(name).or_insert_with(|| DmamuxInput {
+                dmamux: dmamux.to_string(),
+                signal: String::new(),
+                sync_id: None,
+                trigger_id: None,
+            });
+            if is_sync {
+                input.sync_id = Some(id);
+            } else {
+                input.trigger_id = Some(id);
+            }
+        }
+    }
+
+    let mut res: HashMap<String, Vec<DmamuxInput>> = HashMap::new();
+    for (name, mut input) in signals {
+        let Some((peri, signal)) = name.split_once('_') else {
+            anyhow::bail!("{}: {} is not named PERIPHERAL_SIGNAL", path, name);
+        };
+        input.signal = signal.to_string();
+        res.entry(normalize_peri_name(peri).to_string())
+            .or_default()
+            .push(input);
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::perimap::Perimap;
+
+    fn remap(register: &str, field: &str, value: u8) -> RemapInfo {
+        RemapInfo {
+            register: register.to_string(),
+            field: field.to_string(),
+            value,
+        }
+    }
+
+    #[test]
+    fn test_dma_remaps() {
+        let registers = Registers::load(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/registers")).unwrap();
+        let remaps = DmaRemaps::load(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../data/dma_remaps.yaml"),
+            &registers,
+        )
+        .unwrap();
+        let get = |version: &str, peri: &str, signal: &str, dma: u8, channel: u8| {
+            remaps.get(version, &RemapKey::new(peri, signal, dma, channel))
+        };
+
+        // F0x1/F0x2 without DMA_CSELR
+        assert_eq!(
+            get("STM32F072_dma_v1_0", "USART1", "RX", 1, 3),
+            [remap("CFGR1", "USART1_RX_DMA_RMP", 0)]
+        );
+        assert_eq!(
+            get("STM32F072_dma_v1_0", "USART1", "RX", 1, 5),
+            [remap("CFGR1", "USART1_RX_DMA_RMP", 1)]
+        );
+        assert_eq!(
+            get("STM32F072_dma_v1_0", "TIM17", "UP", 1, 7),
+            [remap("CFGR1", "TIM17_DMA_RMP", 0), remap("CFGR1", "TIM17_DMA_RMP2", 1)]
+        );
+
+        // The rules are per DMA version
+        assert_eq!(
+            get("STM32F303_dma_v1_0", "TIM16", "UP", 1, 6),
+            [remap("CFGR1", "TIM16_DMA_RMP", 1)]
+        );
+        assert!(get("STM32F303_dma_v1_0", "USART1", "RX", 1, 5).is_empty());
+        assert!(get("STM32F072_dma_v1_0", "TIM1", "CH4", 1, 6).is_empty());
+        assert!(get("STM32L476_dma_v1_0", "USART1", "RX", 1, 5).is_empty());
+    }
+
+    #[test]
+    fn test_request_select_versions() {
+        let perimap = Perimap::new();
+        let request_select = |chip: &str| {
+            let (kind, version, _) = perimap.get(&format!("{chip}:DMA1:DMA:dma1_v1_0")).unwrap();
+            REQUEST_SELECT_VERSIONS.contains(&(*kind, *version))
+        };
+
+        // F09x and F030xC select requests with DMA_CSELR, so the SYSCFG remaps of the F0 rule never apply to them
+        assert!(request_select("STM32F091RC"));
+        assert!(request_select("STM32F098RC"));
+        assert!(request_select("STM32F030RC"));
+        // The other F0x0, F0x1 and F0x2 rely on the SYSCFG remaps
+        for chip in [
+            "STM32F030R8",
+            "STM32F031K6",
+            "STM32F042K6",
+            "STM32F051R8",
+            "STM32F072RB",
+        ] {
+            assert!(!request_select(chip), "{chip}");
+        }
+
+        assert!(!request_select("STM32F303VC"));
+        assert!(!request_select("STM32F103C8"));
+        assert!(request_select("STM32L476RG"));
+        assert!(request_select("STM32L073RZ"));
+        assert!(request_select("STM32F429ZI"));
+    }
+
+    #[test]
+    fn test_dmamux_inputs() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../data/dmamux");
+        // Every DMAMUX with a request table has an input table
+        for entry in glob::glob(&format!("{dir}/*_DMAMUX*.yaml")).unwrap() {
+            let file = entry.unwrap();
+            let name = file.file_stem().unwrap().to_str().unwrap();
+            let dmamux = name.split_once('_').unwrap().1;
+            load_dmamux_inputs(&format!("{dir}/inputs/{name}.yaml"), dmamux).unwrap();
+        }
+
+        let input = |name: &str, dmamux: &str, peri: &str, signal: &str| {
+            let inputs = load_dmamux_inputs(&format!("{dir}/inputs/{name}.yaml"), dmamux).unwrap();
+            let input = inputs[peri].iter().find(|i| i.signal == signal).unwrap();
+            (input.sync_id, input.trigger_id)
+        };
+        assert_eq!(input("G4_DMAMUX1", "DMAMUX1", "LPTIM1", "OUT"), (Some(20), Some(20)));
+        assert_eq!(input("G0_DMAMUX1", "DMAMUX1", "EXTI", "LINE5"), (Some(5), Some(5)));
+        // DMAMUX2 of H7 has fewer synchronization than trigger inputs, LPTIM4 only triggers
+        assert_eq!(input("H7_DMAMUX2", "DMAMUX2", "LPUART1", "RX_WKUP"), (Some(7), Some(7)));
+        assert_eq!(input("H7_DMAMUX2", "DMAMUX2", "LPTIM4", "AIT"), (None, Some(13)));
+        assert_eq!(input("H7_DMAMUX2", "DMAMUX2", "DMAMUX2", "EVT6"), (Some(6), Some(6)));
+    }
+}
@@ -8,7 +8,7 @@ use log::warn;
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
 use regex::Regex;
-use stm32_data_serde::chip::core::peripheral::{Pin, Trigger};
+use stm32_data_serde::chip::core::peripheral::{InternalTrigger, Pin, Trigger};
 
 use super::*;
 use crate::chips::{Chip, ChipGroup};
@@ -58,11 +58,18 @@ pub fn dump_all_chips(
     af: gpio_af::Af,
     perimap: Perimap,
     stop_modes: low_power::ChipStopModes,
+    low_powers: low_power::PeripheralLowPowers,
     triggers: trigger::Triggers,
+    internal_triggers: trigger::InternalTriggers,
+    remaps: remap::Remaps,
     chip_memories: memory::ChipMemories,
-    blocks: HashMap<String, HashSet<String>>,
+    blocks: HashMap<String, HashMap<String, u32>>,
     chip_interrupts: interrupts::ChipInterrupts,
     peripheral_to_clock: rcc::ParsedRccs,
+    debug_freeze: dbgmcu::ParsedDbgmcus,
+    security: security::ParsedSecurity,
+    capabilities: capability::ParsedCapabilities,
+    boot: boot::ParsedBoot,
     dma_channels: dma::DmaChannels,
     chips: std::collections::HashMap<String, Chip>,
     docs: docs::Docs,
@@ -84,11 +91,18 @@ pub fn dump_all_chips(
             &af,
             &perimap,
             &stop_modes,
+            &low_powers,
             &triggers,
+            &internal_triggers,
+            &remaps,
             &chip_memories,
             &blocks,
             &chip_interrupts,
             &peripheral_to_clock,
+            &debug_freeze,
+            &security,
+            &capabilities,
+            &boot,
             &dma_channels,
             &chips,
             &docs,
@@ -104,11 +118,18 @@ fn process_group(
     af: &gpio_af::Af,
     perimap: &Perimap,
     stop_modes: &low_power::ChipStopModes,
+    low_powers: &low_power::PeripheralLowPowers,
     triggers: &trigger::Triggers,
+    internal_triggers: &trigger::InternalTriggers,
+    remaps: &remap::Remaps,
     chip_memories: &memory::ChipMemories,
-    blocks: &HashMap<String, HashSet<String>>,
+    blocks: &HashMap<String, HashMap<String, u32>>,
     chip_interrupts: &interrupts::ChipInterrupts,
     peripheral_to_clock: &rcc::ParsedRccs,
+    debug_freeze: &dbgmcu::ParsedDbgmcus,
+    security: &security::ParsedSecurity,
+    capabilities: &capability::ParsedCapabilities,
+    boot: &boot::ParsedBoot,
     dma_channels: &dma::DmaChannels,
     chips: &HashMap<String, Chip>,
     docs: &docs::Docs,
@@ -156,10 +177,16 @@ fn process_group(
                 &group,
                 &perimap,
                 &stop_modes,
+                low_powers,
                 &triggers,
+                internal_triggers,
+                remaps,
                 &blocks,
                 chip_interrupts,
                 peripheral_to_clock,
+                debug_freeze,
+                security,
+                capabilities,
                 *rcc_block,
                 chip_af,
                 dma_channels,
@@ -169,8 +196,24 @@ fn process_group(
         .collect();
     let cores = cores?;
 
+    let clock_tree = peripheral_to_clock
+        .clock_tree(rcc_block.1)
+        .map(|clock_tree| clock_tree.name.clone());
+
     for chip_name in &group.chip_names {
-        process_chip(chips, &chip_memories, chip_name, h, docs, &group, &cores)?;
+        process_chip(
+            chips,
+            &chip_memories,
+            boot,
+            remaps,
+            chip_name,
+            h,
+            docs,
+            &group,
+            &cores,
+            &clock_tree,
+        )?;
+        chip_interrupts.audit(chip_name, &cores).write()?;
     }
 
     Ok(())
@@ -184,10 +227,16 @@ fn process_core(
     group: &ChipGroup,
     perimap: &Perimap,
     stop_modes: &low_power::ChipStopModes,
+    low_powers: &low_power::PeripheralLowPowers,
     triggers: &trigger::Triggers,
-    blocks: &HashMap<String, HashSet<String>>,
+    internal_triggers: &trigger::InternalTriggers,
+    remaps: &remap::Remaps,
+    blocks: &HashMap<String, HashMap<String, u32>>,
     chip_interrupts: &interrupts::ChipInterrupts,
     peripheral_to_clock: &rcc::ParsedRccs,
+    debug_freeze: &dbgmcu::ParsedDbgmcus,
+    security: &security::ParsedSecurity,
+    capabilities: &capability::ParsedCapabilities,
     rcc_block: (&str, &str, &str),
     chip_af: Option<&HashMap<String, Vec<stm32_data_serde::chip::core::peripheral::Pin>>>,
     dma_channels: &dma::DmaChannels,
@@ -201,7 +250,10 @@ fn process_core(
         group,
         perimap,
         stop_modes,
+        low_powers,
         triggers,
+        internal_triggers,
+        remaps,
         blocks,
         peripheral_to_clock,
         rcc_block,
@@ -211,6 +263,41 @@ fn process_core(
 
     apply_extras(chip_name, group, extras, &mut peripherals);
 
+    // Internal trigger tables cover the whole family, drop the sources this chip doesn't have.
+    let names: HashSet<_> = peripherals.keys().cloned().collect();
+    for p in peripherals.values_mut() {
+        triggers.check(chip_name, p)?;
+        internal_triggers.check(chip_name, p)?;
+        p.internal_triggers
+            .retain(|t| t.source.split_once('_').is_some_and(|(peri, _)| names.contains(peri)));
+    }
+
+    if let Some(dbgmcu) = peripherals.get("DBGMCU").and_then(|p| p.registers.as_ref()) {
+        let dbgmcu_version = dbgmcu.version.clone();
+        for p in peripherals.values_mut() {
+            p.debug_freeze = debug_freeze.match_peri_freeze(&dbgmcu_version, &p.name);
+        }
+    }
+
+    let version_of = |kind: &str| {
+        peripherals
+            .values()
+            .filter_map(|p| p.registers.as_ref())
+            .find(|r| r.kind == kind)
+            .map(|r| r.version.clone())
+    };
+    let gtzc_version = version_of("gtzc");
+    let rifsc_version = version_of("rifsc");
+    for p in peripherals.values_mut() {
+        p.security = security.match_peri_security(gtzc_version.as_deref(), rifsc_version.as_deref(), &p.name);
+    }
+
+    for p in peripherals.values_mut() {
+        let (caps, provenance) = capabilities.get(chip_name, p);
+        p.capabilities = caps;
+        p.provenance.extend(provenance);
+    }
+
     for p in peripherals.values_mut() {
         // sort and dedup pins, put the ones with AF number first, so we keep them
         p.pins
@@ -221,9 +308,12 @@ fn process_core(
     let mut peripherals: Vec<_> = peripherals.into_values().collect();
     peripherals.sort_by_key(|x| x.name.clone());
 
+    apply_peripheral_sizes(chip_name, &mut peripherals, blocks);
+
     let dmas = collect_dma_instances(group, dma_channels);
     let dma_channels = extract_relevant_dma_channels(&peripherals, &dmas, chip_name);
     associate_peripherals_dma_channels(&mut peripherals, dmas, &dma_channels);
+    dma::check_channel_assignments(chip_name, &peripherals)?;
 
     let mut pins: Vec<_> = group
         .pins
@@ -241,6 +331,7 @@ fn process_core(
         peripherals,
         nvic_priority_bits: None,
         interrupts: vec![],
+        vector_table: None,
         dma_channels,
         pins,
     };
@@ -265,8 +356,11 @@ fn create_peripherals_for_chip(
     group: &ChipGroup,
     perimap: &Perimap,
     stop_modes: &low_power::ChipStopModes,
+    low_powers: &low_power::PeripheralLowPowers,
     triggers: &trigger::Triggers,
-    blocks: &HashMap<String, HashSet<String>>,
+    internal_triggers: &trigger::InternalTriggers,
+    remaps: &remap::Remaps,
+    blocks: &HashMap<String, HashMap<String, u32>>,
     peripheral_to_clock: &rcc::ParsedRccs,
     rcc_block: (&str, &str, &str),
     chip_af: Option<&HashMap<String, Vec<Pin>>>,
@@ -283,6 +377,7 @@ fn create_peripherals_for_chip(
 
         let addr = resolve_peri_addr(chip_name, &pname, defines);
         let Some(address) = addr else { continue };
+        let secure_address = resolve_peri_secure_addr(chip_name, &pname, address, defines);
 
         let perimap = perimap.get(&format!("{chip_name}:{pname}:{pkind}"));
         let registers = if let Some(&block) = perimap {
@@ -300,7 +395,7 @@ fn create_peripherals_for_chip(
                 panic!("failed to get parsed {}_{} regisers", registers.kind, registers.version);
             };
 
-            if !blocks.contains(&registers.block) {
+            if !blocks.contains_key(&registers.block) {
                 panic!(
                     "failed to get parsed block {} from {}_{} regisers",
                     registers.block, registers.kind, registers.version
@@ -326,24 +421,45 @@ fn create_peripherals_for_chip(
             }
         };
 
+        let mut provenance = Vec::new();
         let rcc = if let Some(mut rcc_info) = peripheral_to_clock
             .match_peri_clock(rcc_block.1, &pname)
             .or_else(syscfg_for_comp)
         {
-            if let Some(stop_mode_info) = stop_modes.peripheral_stop_mode_info(chip_name, &pname) {
-                rcc_info.stop_mode = stop_mode_info;
+            if let Some(rule) = stop_modes.peripheral_stop_mode_rule(chip_name, &pname) {
+                rcc_info.stop_mode = rule.stop_mode.clone();
+                provenance.push(rule.source.provenance("stop_mode"));
+            }
+            if let Some((low_power, source)) = low_powers.get(chip_name, &pname) {
+                rcc_info.low_power = Some(low_power.clone());
+                provenance.push(source.provenance("low_power"));
             }
             Some(rcc_info)
         } else {
             None
         };
 
-        let triggers = if let Some(triggers) = triggers.peripheral_trigger_info(chip_name, &pname) {
-            triggers
+        let internal_triggers = if let Some(rule) = internal_triggers.peripheral_itr_info(chip_name, &pname) {
+            provenance.push(rule.source.provenance("internal_triggers"));
+            rule.itr
+                .iter()
+                .map(|(&itr, source)| InternalTrigger {
+                    itr,
+                    source: source.clone(),
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let triggers = if let Some(rule) = triggers.peripheral_trigger_info(chip_name, &pname) {
+            provenance.push(rule.source.provenance("triggers"));
+            rule.triggers
                 .iter()
                 .map(|trigger| Trigger {
-                    signal: trigger.signal.to_string(),
-                    source: trigger.source.to_string(),
+                    signal: trigger.signal.clone(),
+                    source: trigger.source.clone(),
+                    field: trigger.field.as_ref().map(|(field, _)| field.clone()),
+                    value: trigger.field.as_ref().map(|&(_, value)| value),
                 })
                 .collect()
         } else {
@@ -353,138 +469,45 @@ fn create_peripherals_for_chip(
         let mut pins = merge_afs_into_core_pins(chip_name, chip_af, &periph_pins, &pname);
         pins.append(&mut merge_i2s_into_spi_pins(chip_name, chip_af, &periph_pins, &pname));
 
-        let afio = if chip_name.starts_with("STM32F1") {
-            let peripheral = if pname == "CAN" {
-                "CAN1".to_string()
-            } else {
-                pname.replace("I2S", "SPI")
-            };
-
-            macro_rules! afio {
-                ($value:expr, $($pins:expr),+) => {
-                    stm32_data_serde::chip::core::peripheral::AfioValue {
-                        value: $value,
-                        pins: vec![$($pins.to_string()),+],
-                    }
-                }
-            }
-
-            #[rustfmt::skip]
-            let afio_mapr = match peripheral.as_str() {
-                "SPI3" => vec![
-                    afio!(0, "PC7", "PA15", "PB3", "PB4", "PB5"),
-                    afio!(1, "PC7", "PA4", "PC10", "PC11", "PC12"),
-                ],
-                "CAN2" => vec![
-                    afio!(0, "PB12", "PB13"),
-                    afio!(1, "PB5", "PB6"),
-                ],
-                "ETH" => vec![
-                    afio!(0, "PA0", "PA1", "PA2", "PA3", "PC1", "PC2", "PC3", "PB5", "PB8", "PB10", "PB11", "PB12", "PB13", "PA7", "PC4", "PC5", "PB0", "PB1"),
-                    afio!(1, "PA0", "PA1", "PA2", "PA3", "PC1", "PC2", "PC3", "PB5", "PB8", "PB10", "PB11", "PB12", "PB13", "PD8", "PD9", "PD10", "PD11", "PD12"),
-                ],
-                "CAN1" => vec![
-                    afio!(0, "PA11", "PA12"),
-                    afio!(2, "PB8", "PB9"),
-                    afio!(3, "PD0", "PD1"),
-                ],
-                "TIM4" => vec![
-                    afio!(0, "PE0", "PB6", "PB7", "PB8", "PB9"),
-                    afio!(1, "PE0", "PD12", "PD13", "PD14", "PD15"),
-                ],
-                "TIM3" => vec![
-                    afio!(0, "PD2", "PA6", "PA7", "PB0", "PB1"),
-                    afio!(2, "PD2", "PB4", "PB5", "PB0", "PB1"),
-                    afio!(3, "PD2", "PC6", "PC7", "PC8", "PC9"),
-                ],
-                "TIM2" => vec![
-                    afio!(0, "PA0", "PA1", "PA2", "PA3"),
-                    afio!(1, "PA15", "PB3", "PA2", "PA3"),
-                    afio!(2, "PA0", "PA1", "PB10", "PB11"),
-                    afio!(3, "PA15", "PB3", "PB10", "PB11"),
-                ],
-                "TIM1" => vec![
-                    afio!(0, "PA12", "PA8", "PA9", "PA10", "PA11", "PB12", "PB13", "PB14", "PB15"),
-                    afio!(1, "PA12", "PA8", "PA9", "PA10", "PA11", "PA6", "PA7", "PB0", "PB1"),
-                    afio!(3, "PE7", "PE9", "PE11", "PE13", "PE14", "PE15", "PE8", "PE10", "PE12"),
-                ],
-                "USART3" => vec![
-                    afio!(0, "PB10", "PB11", "PB12", "PB13", "PB14"),
-                    afio!(1, "PC10", "PC11", "PC12", "PB13", "PB14"),
-                    afio!(3, "PD8", "PD9", "PD10", "PD11", "PD12"),
-                ],
-                "USART2" => vec![
-                    afio!(0, "PA0", "PA1", "PA2", "PA3", "PA4"),
-                    afio!(1, "PD3", "PD4", "PD5", "PD6", "PD7"),
-                ],
-                "USART1" => vec![
-                    afio!(0, "PA11", "PA12", "PA8", "PA9", "PA10"),
-                    afio!(1, "PA11", "PA12", "PA8", "PB6", "PB7"),
-                ],
-                "I2C1" => vec![
-                    afio!(0, "PB6", "PB7"),
-                    afio!(1, "PB8", "PB9"),
-                ],
-                "SPI1" => vec![
-                    afio!(0, "PA4", "PA5", "PA6", "PA7"),
-                    afio!(1, "PA15", "PB3", "PB4", "PB5"),
-                ],
-                _ => vec![],
-            };
-
-            let afio_mapr2 = if chip_name.starts_with("STM32F100") {
-                match peripheral.as_str() {
-                    "TIM12" => vec![afio!(0, "PC4", "PC5"), afio!(1, "PB12", "PB13")],
-                    "TIM14" => vec![afio!(0, "PC9"), afio!(1, "PB1")],
-                    "TIM13" => vec![afio!(0, "PC8"), afio!(1, "PB0")],
-                    "CEC" => vec![afio!(0, "PB8"), afio!(1, "PB10")],
-                    "TIM17" => vec![afio!(0, "PA10", "PB7", "PB9"), afio!(1, "PA10", "PB7", "PA7")],
-                    "TIM16" => vec![afio!(0, "PB5", "PB8"), afio!(1, "PB5", "PA6")],
-                    "TIM15" => vec![afio!(0, "PA9", "PA2", "PA3"), afio!(1, "PA9", "PB14", "PB15")],
-                    _ => vec![],
-                }
-            } else {
-                // STM32F10[12357]
-                match peripheral.as_str() {
-                    "TIM14" => vec![afio!(0, "PA7"), afio!(1, "PF9")],
-                    "TIM13" => vec![afio!(0, "PA6"), afio!(1, "PF8")],
-                    "TIM11" => vec![afio!(0, "PB9"), afio!(1, "PF7")],
-                    "TIM10" => vec![afio!(0, "PB8"), afio!(1, "PF6")],
-                    "TIM9" => vec![afio!(0, "PA2", "PA3"), afio!(1, "PE5", "PE6")],
-                    _ => vec![],
-                }
-            };
-
-            let field = format!("{peripheral}_REMAP");
-            if afio_mapr.len() > 0 {
-                Some(stm32_data_serde::chip::core::peripheral::Afio {
-                    register: "MAPR".to_string(),
-                    field,
-                    values: afio_mapr,
-                })
-            } else if afio_mapr2.len() > 0 {
-                Some(stm32_data_serde::chip::core::peripheral::Afio {
-                    register: "MAPR2".to_string(),
-                    field,
-                    values: afio_mapr2,
-                })
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        let remaps = remaps.get(chip_name, &pname);
+        let afio =
+            remaps
+                .iter()
+                .find(|r| r.peripheral == "AFIO")
+                .map(|r| stm32_data_serde::chip::core::peripheral::Afio {
+                    register: r.register.clone(),
+                    field: r.field.clone(),
+                    values: r
+                        .values
+                        .iter()
+                        .map(|v| stm32_data_serde::chip::core::peripheral::AfioValue {
+                            value: v.value,
+                            pins: v.pins.clone(),
+                        })
+                        .collect(),
+                });
 
         let p = stm32_data_serde::chip::core::Peripheral {
             name: pname.clone(),
             address,
+            secure_address,
+            size: None,
+            size_inferred: false,
             registers,
             rcc,
+            debug_freeze: None,
+            security: None,
+            capabilities: None,
             interrupts: Vec::new(),
             dma_channels: Vec::new(),
+            dmamux_inputs: Vec::new(),
+            dma_triggers: Vec::new(),
             triggers: triggers,
+            internal_triggers,
             pins,
+            remaps,
             afio,
+            provenance,
         };
 
         peripherals.insert(p.name.clone(), p);
@@ -862,6 +885,26 @@ fn resolve_peri_addr(chip_name: &str, pname: &str, defines: &header::Defines) ->
     }
 }
 
+/// Resolve the secure alias of a peripheral on TrustZone chips.
+///
+/// `address` is the (non-secure) address returned by [resolve_peri_addr]. The headers only give
+/// us `_BASE_S` defines for the "plain" peripheral names, so we take the secure/non-secure offset
+/// from them and apply it to `address`, which keeps the fixups done in [resolve_peri_addr].
+fn resolve_peri_secure_addr(chip_name: &str, pname: &str, address: u32, defines: &header::Defines) -> Option<u32> {
+    let lookup = if pname.starts_with("FDCANRAM") {
+        "FDCANRAM"
+    } else if pname == "VREFINTCAL" && chip_name.starts_with("STM32WBA") {
+        "DESIG"
+    } else {
+        pname
+    };
+
+    let secure = defines.get_peri_secure_addr(lookup)?;
+    let nonsecure = defines.get_peri_addr(lookup)?;
+    let offset = secure.checked_sub(nonsecure).filter(|&offset| offset != 0)?;
+    address.checked_add(offset)
+}
+
 /// Merge YAML overlays into the peripheral map for the current chip.
 ///
 /// Iterates all loaded extras and applies those whose `matches` field matches the current chip.
@@ -963,6 +1006,48 @@ fn apply_extras(
     }
 }
 
+/// Fill in the size of the address range of each peripheral.
+///
+/// The size is the extent of the peripheral's register block. Peripherals without registers
+/// get the distance to the next peripheral base address instead, and so do the ones whose block
+/// runs into the next peripheral. Both are marked as inferred.
+fn apply_peripheral_sizes(
+    chip_name: &str,
+    peripherals: &mut [stm32_data_serde::chip::core::Peripheral],
+    blocks: &HashMap<String, HashMap<String, u32>>,
+) {
+    let mut bases: Vec<u32> = peripherals.iter().map(|p| p.address).collect();
+    bases.sort();
+    bases.dedup();
+
+    for p in peripherals.iter_mut() {
+        let block_size = p.registers.as_ref().and_then(|r| {
+            blocks
+                .get(&format!("{}_{}", r.kind, r.version))
+                .and_then(|b| b.get(&r.block))
+                .copied()
+                .filter(|&size| size != 0)
+        });
+        // Aliases at the same base address (e.g. SPI/I2S) are intentional.
+        let spacing = bases.iter().find(|&&b| b > p.address).map(|&b| b - p.address);
+        (p.size, p.size_inferred) = match (block_size, spacing) {
+            (Some(size), Some(spacing)) if size > spacing => {
+                warn!(
+                    "{}: {} ({:#x}..{:#x}) overlaps the peripheral at {:#x}, using the spacing",
+                    chip_name,
+                    p.name,
+                    p.address,
+                    p.address as u64 + size as u64,
+                    p.address + spacing
+                );
+                (Some(spacing), true)
+            }
+            (Some(size), _) => (Some(size), false),
+            (None, spacing) => (spacing, spacing.is_some()),
+        };
+    }
+}
+
 /// Collect and sort all DMA IP instances available on the current chip.
 ///
 /// Iterates over the parsed MCU IP definitions (`group.ips`), matching each IP’s `version`
@@ -1053,13 +1138,15 @@ fn extract_relevant_dma_channels(
 /// the sorted list to the peripheral’s `dma_channels` field.
 ///
 /// This determines which DMA channels can be used for data transfers for each peripheral.
-/// Modifies each `Peripheral` in-place, setting `peripheral.dma_channels`.
+/// Modifies each `Peripheral` in-place, setting `peripheral.dma_channels`, as well as the DMAMUX
+/// inputs and GPDMA hardware triggers it drives.
 fn associate_peripherals_dma_channels(
     peripherals: &mut Vec<stm32_data_serde::chip::core::Peripheral>,
     dmas: Vec<(String, String, &dma::ChipDma)>,
     dma_channels: &Vec<stm32_data_serde::chip::core::DmaChannels>,
 ) {
     let have_chs: HashSet<_> = dma_channels.iter().map(|ch| ch.name.clone()).collect();
+    let have_dmas: HashSet<_> = dma_channels.iter().map(|ch| ch.dma.clone()).collect();
 
     // Process peripheral - DMA channel associations
     for p in peripherals {
@@ -1079,30 +1166,71 @@ fn associate_peripherals_dma_channels(
         }
         chs.sort_by_key(|ch| (ch.channel.clone(), ch.dmamux.clone(), ch.request));
         p.dma_channels.append(&mut chs);
+
+        let mut inputs: Vec<_> = dmas
+            .iter()
+            .filter_map(|(_, _, dma)| dma.dmamux_inputs.get(&p.name))
+            .flatten()
+            .cloned()
+            .collect();
+        inputs.sort();
+        inputs.dedup();
+        p.dmamux_inputs.append(&mut inputs);
+
+        let mut triggers: Vec<_> = dmas
+            .iter()
+            .filter_map(|(_, _, dma)| dma.triggers.get(&p.name))
+            .flatten()
+            .filter(|t| have_dmas.contains(&t.dma))
+            .cloned()
+            .collect();
+        triggers.sort();
+        triggers.dedup();
+        p.dma_triggers.append(&mut triggers);
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn process_chip(
     chips: &HashMap<String, Chip>,
     chip_memories: &memory::ChipMemories,
+    boot: &boot::ParsedBoot,
+    remaps: &remap::Remaps,
     chip_name: &str,
-    _h: &header::ParsedHeader,
+    h: &header::ParsedHeader,
     docs: &docs::Docs,
     group: &ChipGroup,
     cores: &[stm32_data_serde::chip::Core],
+    clock_tree: &Option<String>,
 ) -> Result<(), anyhow::Error> {
     let chip = chips.get(chip_name).unwrap();
     let docs = docs.documents_for(chip_name);
+    let mut memory = chip_memories.get(chip_name)?;
+    memory::apply_secure_aliases(&mut memory, h.get_defines("all"));
+    let peripherals = cores
+        .iter()
+        .flat_map(|c| &c.peripherals)
+        .map(|p| (p.name.clone(), p.address))
+        .collect();
+    memory::add_external_windows(chip_name, &mut memory, h.get_defines("all"), &peripherals);
+    memory::add_system_regions(chip_name, &mut memory, h.get_defines("all"));
+    let multicore = crate::multicore::multicore(chip_name, &memory, cores);
     let chip = stm32_data_serde::Chip {
         name: chip_name.to_string(),
         family: group.family.clone(),
         line: group.line.clone(),
         die: group.die.clone(),
         device_id: u16::from_str_radix(&group.die[3..], 16).unwrap(),
+        clock_tree: clock_tree.clone(),
         packages: chip.packages.clone(),
-        memory: chip_memories.get(chip_name),
+        memory,
+        flash: chip_memories.flash(chip_name)?,
+        boot: boot.boot(&chip.packages, group, cores),
+        wakeup_pins: boot.wakeup_pins(chip_name, cores),
+        pad_remaps: remaps.pads(chip_name, &cores[0].pins),
         docs,
         cores: cores.to_vec(),
+        multicore,
     };
 
     crate::check::check(&chip);
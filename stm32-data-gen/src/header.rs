@@ -192,6 +192,34 @@ impl Defines {
     }
 
     pub fn get_peri_addr(&self, pname: &str) -> Option<u32> {
+        Self::possible_peri_defines(pname)
+            .into_iter()
+            .find_map(|d| self.0.get(&d).filter(|&&addr| addr != 0))
+            .map(|x| u32::try_from(*x).unwrap())
+    }
+
+    /// Get the secure alias address of a peripheral on TrustZone chips.
+    ///
+    /// TrustZone headers define both `<PERI>_BASE_NS` and `<PERI>_BASE_S`. `<PERI>_BASE`
+    /// (what [`Self::get_peri_addr`] returns) resolves to the non-secure alias, so here we
+    /// look up the `_S` variant of every candidate define instead.
+    pub fn get_peri_secure_addr(&self, pname: &str) -> Option<u32> {
+        Self::possible_peri_defines(pname)
+            .into_iter()
+            .map(|d| {
+                if let Some(base) = d.strip_suffix("_NS") {
+                    format!("{base}_S")
+                } else if d.ends_with("_S") {
+                    d
+                } else {
+                    format!("{d}_S")
+                }
+            })
+            .find_map(|d| self.0.get(&d).filter(|&&addr| addr != 0))
+            .map(|x| u32::try_from(*x).unwrap())
+    }
+
+    fn possible_peri_defines(pname: &str) -> Vec<String> {
         const ALT_PERI_DEFINES: &[(&str, &[&str])] = &[
             ("DBGMCU", &["DBGMCU_BASE", "DBG_BASE"]),
             (
@@ -321,14 +349,10 @@ impl Defines {
         ];
         let alt_peri_defines: HashMap<_, _> = ALT_PERI_DEFINES.iter().copied().collect();
 
-        let possible_defines: Vec<String> = alt_peri_defines
+        alt_peri_defines
             .get(pname)
             .map(|x| x.iter().map(ToString::to_string).collect())
-            .unwrap_or_else(|| vec![format!("{pname}_BASE"), pname.to_string()]);
-        possible_defines
-            .into_iter()
-            .find_map(|d| self.0.get(&d).filter(|&&addr| addr != 0))
-            .map(|x| u32::try_from(*x).unwrap())
+            .unwrap_or_else(|| vec![format!("{pname}_BASE"), pname.to_string()])
     }
 }
 
@@ -1,14 +1,16 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::sync::LazyLock;
 use std::thread;
 
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 use lazy_regex::regex;
 use log::*;
+use serde::{Deserialize, Serialize};
+use stm32_data_serde::chip::core::peripheral::ExtiLines;
 
 use crate::chips::ChipGroup;
 use crate::normalize_peris::normalize_peri_name;
-use crate::util::new_regex_map;
+use crate::util::{RuleSource, new_regex_map};
 
 mod xml {
     use serde::Deserialize;
@@ -45,6 +47,9 @@ pub struct ChipInterrupts {
     // (nvic name, nvic version) => [cursed unparsed interrupt string]
     pub irqs: HashMap<(String, String), Vec<String>>,
     signals: InterruptSignals,
+    /// Peripherals, without their instance number, that have an interrupt on any chip
+    irq_peripherals: HashSet<String>,
+    shared_exti_lines: SharedExtiLines,
 }
 
 impl ChipInterrupts {
@@ -78,7 +83,30 @@ impl ChipInterrupts {
 
         let signals = InterruptSignals::new();
 
-        Ok(Self { irqs, signals })
+        // Peripherals named by the vector or listed as its owners (or DMA controllers) in any NVIC file.
+        let irq_peripherals = irqs
+            .values()
+            .flatten()
+            .flat_map(|irq| {
+                let parts: Vec<&str> = irq.split(':').collect();
+                let name = parts[0].strip_suffix("_IRQn").unwrap_or(parts[0]);
+                let owners = parts
+                    .iter()
+                    .skip(2)
+                    .take(2)
+                    .flat_map(|p| p.split(','))
+                    .map(ToString::to_string);
+                tokenize_name(name).into_iter().chain(owners).collect::<Vec<_>>()
+            })
+            .map(|p| trim_trailing_digits(normalize_peri_name(&p)).to_string())
+            .collect();
+
+        Ok(Self {
+            irqs,
+            signals,
+            irq_peripherals,
+            shared_exti_lines: SharedExtiLines::parse()?,
+        })
     }
 
     pub(crate) fn process(
@@ -135,6 +163,8 @@ impl ChipInterrupts {
             })
             .collect();
         core.interrupts.sort_unstable_by_key(|x| x.number);
+        let sau = header_defines.0.get("__SAUREGION_PRESENT").is_some_and(|&n| n != 0);
+        core.vector_table = vector_table(&core.name, sau, &core.interrupts);
 
         // =================== Populate peripheral interrupts
         let core_name = &core.name;
@@ -573,9 +603,27 @@ impl ChipInterrupts {
                     }
 
                     for irq in irqs {
+                        let exti_lines = if p.name == "EXTI" {
+                            exti_lines(&irq)
+                        } else {
+                            self.shared_exti_lines.get(chip_name, &p.name, signal)
+                        };
+                        if let Some(lines) = &exti_lines
+                            && let Some(line) = regex!(r"^EXTI(\d+)$").captures(signal)
+                            && !(lines.first..=lines.last).contains(&line[1].parse()?)
+                        {
+                            return Err(anyhow!(
+                                "EXTI signal {} is not served by {} in {}",
+                                signal,
+                                irq,
+                                chip_name
+                            ));
+                        }
+
                         all_irqs.push(stm32_data_serde::chip::core::peripheral::Interrupt {
                             signal: signal.clone(),
                             interrupt: irq,
+                            exti_lines,
                         })
                     }
                 }
@@ -598,6 +646,303 @@ impl ChipInterrupts {
     }
 }
 
+/// Interrupt assignment audit of a chip, written to `build/data/interrupt_audit`.
+#[derive(Serialize)]
+pub struct InterruptAudit {
+    chip: String,
+    cores: Vec<CoreInterruptAudit>,
+}
+
+#[derive(Serialize)]
+struct CoreInterruptAudit {
+    core: String,
+    /// Vectors in the header that no peripheral signal was assigned to
+    orphaned_vectors: Vec<String>,
+    /// Peripherals without any interrupt, leaving out the ones that have no interrupt on any chip
+    peripherals_without_interrupts: Vec<String>,
+    /// Peripheral -> signals a header vector is named after, but that were not assigned to it
+    missing_signals: BTreeMap<String, Vec<String>>,
+    /// Vector -> peripherals of different kinds it was assigned to
+    shared_vectors: BTreeMap<String, Vec<String>>,
+}
+
+impl InterruptAudit {
+    pub fn write(&self) -> anyhow::Result<()> {
+        std::fs::create_dir_all("build/data/interrupt_audit")?;
+        let dump = serde_json::to_string_pretty(self)?;
+        std::fs::write(format!("build/data/interrupt_audit/{}.json", self.chip), dump)?;
+        Ok(())
+    }
+}
+
+impl ChipInterrupts {
+    /// Audit the interrupt assignment of the processed cores of a chip.
+    pub(crate) fn audit(&self, chip_name: &str, cores: &[stm32_data_serde::chip::Core]) -> InterruptAudit {
+        let cores = cores
+            .iter()
+            .map(|core| {
+                let mut vector_peris: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+                let mut peripherals_without_interrupts = Vec::new();
+                let mut missing_signals = BTreeMap::new();
+
+                for p in &core.peripherals {
+                    for i in &p.interrupts {
+                        vector_peris.entry(&i.interrupt).or_default().insert(&p.name);
+                    }
+                    if p.interrupts.is_empty() {
+                        // GPIO, SYSCFG, DBGMCU and the like have no interrupt on any chip.
+                        if self.irq_peripherals.contains(trim_trailing_digits(&p.name)) {
+                            peripherals_without_interrupts.push(p.name.clone());
+                        }
+                        continue;
+                    }
+
+                    // The signals the header names a vector of this instance after, e.g. BRK for
+                    // TIM1_BRK_TIM9. Vectors named after the instance only (TIM2) serve every signal.
+                    let known = self.signals.valid_signals(&p.name, chip_name);
+                    let mut offered = BTreeSet::new();
+                    for i in &core.interrupts {
+                        let tokens = tokenize_name(&i.name);
+                        let Some(pos) = tokens.iter().position(|t| *t == p.name) else {
+                            continue;
+                        };
+                        for token in &tokens[pos + 1..] {
+                            let Some(signal) = known.get(token.as_str()) else {
+                                break;
+                            };
+                            offered.insert(*signal);
+                        }
+                    }
+
+                    let have: HashSet<_> = p.interrupts.iter().map(|i| i.signal.as_str()).collect();
+                    let missing: Vec<String> = offered
+                        .into_iter()
+                        .filter(|s| !have.contains(s))
+                        .map(ToString::to_string)
+                        .collect();
+                    if !missing.is_empty() {
+                        missing_signals.insert(p.name.clone(), missing);
+                    }
+                }
+
+                let orphaned_vectors = core
+                    .interrupts
+                    .iter()
+                    .filter(|i| !vector_peris.contains_key(i.name.as_str()))
+                    .map(|i| i.name.clone())
+                    .collect();
+
+                // Instances of the same kind (e.g. USART3_4, DMA1_Channel2_3) sharing a vector is expected.
+                let kind_of = |name: &str| {
+                    core.peripherals
+                        .iter()
+                        .find(|p| p.name == name)
+                        .and_then(|p| p.registers.as_ref())
+                        .map_or_else(|| trim_trailing_digits(name).to_string(), |r| r.kind.clone())
+                };
+                let shared_vectors = vector_peris
+                    .into_iter()
+                    .filter(|(_, peris)| peris.iter().map(|p| kind_of(p)).collect::<HashSet<_>>().len() > 1)
+                    .map(|(irq, peris)| (irq.to_string(), peris.into_iter().map(ToString::to_string).collect()))
+                    .collect();
+
+                CoreInterruptAudit {
+                    core: core.name.clone(),
+                    orphaned_vectors,
+                    peripherals_without_interrupts,
+                    missing_signals,
+                    shared_vectors,
+                }
+            })
+            .collect();
+
+        InterruptAudit {
+            chip: chip_name.to_string(),
+            cores,
+        }
+    }
+}
+
+/// Cortex-M architecture version of a core.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Architecture {
+    V6M,
+    V7M,
+    V8MBaseline,
+    V8MMainline,
+}
+
+/// Short core names and their architecture.
+#[rustfmt::skip]
+const CORE_ARCHITECTURES: &[(&str, Architecture)] = &[
+    ("cm0",   Architecture::V6M),
+    ("cm0p",  Architecture::V6M),
+    ("cm3",   Architecture::V7M),
+    ("cm4",   Architecture::V7M),
+    ("cm7",   Architecture::V7M),
+    ("cm23",  Architecture::V8MBaseline),
+    ("cm33",  Architecture::V8MMainline),
+    ("cm35p", Architecture::V8MMainline),
+    ("cm55",  Architecture::V8MMainline),
+    ("cm85",  Architecture::V8MMainline),
+];
+
+/// Build the full vector table of a core from the system exceptions of its architecture and the
+/// device interrupts. `sau` is set when the header has `__SAUREGION_PRESENT`, i.e. the core
+/// implements TrustZone. Returns `None` for cores that aren't Cortex-M.
+fn vector_table(
+    core_name: &str,
+    sau: bool,
+    interrupts: &[stm32_data_serde::chip::core::Interrupt],
+) -> Option<stm32_data_serde::chip::core::VectorTable> {
+    // ARMv6-M and ARMv8-M Baseline only have the mandatory exceptions, ARMv7-M adds the configurable
+    // faults and the debug monitor, and ARMv8-M Mainline adds SecureFault when TrustZone is implemented.
+    const BASELINE: &[(&str, u8)] = &[
+        ("NonMaskableInt", 2),
+        ("HardFault", 3),
+        ("SVCall", 11),
+        ("PendSV", 14),
+        ("SysTick", 15),
+    ];
+    const MAINLINE: &[(&str, u8)] = &[
+        ("NonMaskableInt", 2),
+        ("HardFault", 3),
+        ("MemoryManagement", 4),
+        ("BusFault", 5),
+        ("UsageFault", 6),
+        ("SVCall", 11),
+        ("DebugMonitor", 12),
+        ("PendSV", 14),
+        ("SysTick", 15),
+    ];
+    const MAINLINE_V8: &[(&str, u8)] = &[
+        ("NonMaskableInt", 2),
+        ("HardFault", 3),
+        ("MemoryManagement", 4),
+        ("BusFault", 5),
+        ("UsageFault", 6),
+        ("SecureFault", 7),
+        ("SVCall", 11),
+        ("DebugMonitor", 12),
+        ("PendSV", 14),
+        ("SysTick", 15),
+    ];
+
+    let &(_, architecture) = CORE_ARCHITECTURES.iter().find(|(name, _)| *name == core_name)?;
+    let exceptions = match architecture {
+        Architecture::V6M | Architecture::V8MBaseline => BASELINE,
+        Architecture::V7M => MAINLINE,
+        Architecture::V8MMainline if sau => MAINLINE_V8,
+        Architecture::V8MMainline => MAINLINE,
+    };
+
+    let irq_count = interrupts.iter().map(|i| u16::from(i.number) + 1).max().unwrap_or(0);
+    let entries = 16 + irq_count;
+
+    let used: HashSet<u16> = exceptions
+        .iter()
+        .map(|&(_, number)| u16::from(number))
+        .chain(interrupts.iter().map(|i| 16 + u16::from(i.number)))
+        .collect();
+    // Entry 0 is the initial stack pointer, not a handler.
+    let reserved = (1..entries).filter(|n| !used.contains(n)).collect();
+
+    // VTOR needs the table aligned to its size rounded up to a power of two, and to at least 32 words.
+    // The Cortex-M0 has no VTOR, its table can't be moved from address 0.
+    let alignment = (core_name != "cm0").then(|| (u32::from(entries) * 4).next_power_of_two().max(128));
+
+    Some(stm32_data_serde::chip::core::VectorTable {
+        entries,
+        alignment,
+        exceptions: exceptions
+            .iter()
+            .map(|&(name, number)| stm32_data_serde::chip::core::Exception {
+                name: name.to_string(),
+                number,
+            })
+            .collect(),
+        reserved,
+    })
+}
+
+/// A rule of `data/exti_lines.yaml`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SharedExtiLineRule {
+    /// Regex on `chip:peripheral:signal`
+    #[serde(rename = "match")]
+    pattern: String,
+    source: RuleSource,
+    lines: ExtiLines,
+}
+
+/// EXTI lines of interrupt signals of peripherals other than EXTI, e.g. PVD or RTC wakeup.
+struct SharedExtiLines {
+    map: regex_map::RegexMap<ExtiLines>,
+}
+
+impl std::fmt::Debug for SharedExtiLines {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedExtiLines").finish_non_exhaustive()
+    }
+}
+
+impl SharedExtiLines {
+    /// Load the EXTI lines of shared interrupts from `data/exti_lines.yaml`. The first matching rule wins.
+    fn parse() -> anyhow::Result<Self> {
+        Self::load("data/exti_lines.yaml")
+    }
+
+    fn load(path: &str) -> anyhow::Result<Self> {
+        let rules: Vec<SharedExtiLineRule> =
+            serde_yaml::from_str(&std::fs::read_to_string(path).context(path.to_string())?)
+                .context(path.to_string())?;
+
+        for rule in &rules {
+            regex::Regex::new(&rule.pattern).with_context(|| format!("{}: {}", path, rule.pattern))?;
+            rule.source
+                .validate()
+                .with_context(|| format!("{}: {}", path, rule.pattern))?;
+            if rule.lines.first > rule.lines.last {
+                anyhow::bail!(
+                    "{}: {}: line {} comes after {}",
+                    path,
+                    rule.pattern,
+                    rule.lines.first,
+                    rule.lines.last
+                );
+            }
+        }
+        RuleSource::check_unverified(path, rules.iter().map(|r| &r.source))?;
+
+        Ok(Self {
+            map: new_regex_map(rules.into_iter().map(|r| (r.pattern, r.lines))),
+        })
+    }
+
+    /// Get the EXTI lines raising an interrupt signal of a peripheral.
+    fn get(&self, chip_name: &str, peripheral: &str, signal: &str) -> Option<ExtiLines> {
+        self.map
+            .get(&format!("{chip_name}:{peripheral}:{signal}"))
+            .next()
+            .cloned()
+    }
+}
+
+/// Get the EXTI lines served by an EXTI interrupt vector from its name.
+///
+/// Vectors are named after the lines they serve, either a single one (`EXTI0`, `EXTI2_TSC`)
+/// or a range in either order (`EXTI9_5`, `EXTI15_10`, `EXTI4_15`).
+fn exti_lines(irq: &str) -> Option<ExtiLines> {
+    let m = regex!(r"^EXTI(\d+)(?:_(\d+))?(?:_[A-Z]\w*)?$").captures(irq)?;
+    let a: u8 = m[1].parse().ok()?;
+    let b: u8 = m.get(2).map_or(Some(a), |b| b.as_str().parse().ok())?;
+    Some(ExtiLines {
+        first: a.min(b),
+        last: a.max(b),
+    })
+}
+
 fn tokenize_name(name: &str) -> Vec<String> {
     // Treat IRQ names are "tokens" separated by `_`, except some tokens
     // contain `_` themselves, such as `C1_RX`.
@@ -864,3 +1209,99 @@ fn pick_nvic(chip_name: &str, core_name: &str) -> String {
         .unwrap()
         .to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interrupts(count: u8) -> Vec<stm32_data_serde::chip::core::Interrupt> {
+        (0..count)
+            .map(|number| stm32_data_serde::chip::core::Interrupt {
+                name: format!("IRQ{number}"),
+                number,
+            })
+            .collect()
+    }
+
+    fn exception_numbers(table: &stm32_data_serde::chip::core::VectorTable) -> Vec<u8> {
+        table.exceptions.iter().map(|e| e.number).collect()
+    }
+
+    #[test]
+    fn test_vector_table() {
+        // Cortex-M0: baseline exceptions and no VTOR
+        let cm0 = vector_table("cm0", false, &interrupts(32)).unwrap();
+        assert_eq!(cm0.entries, 48);
+        assert_eq!(cm0.alignment, None);
+        assert_eq!(exception_numbers(&cm0), [2, 3, 11, 14, 15]);
+        assert_eq!(cm0.reserved, [1, 4, 5, 6, 7, 8, 9, 10, 12, 13]);
+
+        // 98 entries take 392 bytes, so the table is aligned to 512
+        let cm4 = vector_table("cm4", false, &interrupts(82)).unwrap();
+        assert_eq!(cm4.entries, 98);
+        assert_eq!(cm4.alignment, Some(512));
+        assert_eq!(exception_numbers(&cm4), [2, 3, 4, 5, 6, 11, 12, 14, 15]);
+
+        // Small tables still need 32-word alignment
+        assert_eq!(
+            vector_table("cm0p", false, &interrupts(8)).unwrap().alignment,
+            Some(128)
+        );
+
+        // SecureFault only exists on ARMv8-M Mainline cores with a SAU
+        let cm33 = vector_table("cm33", false, &interrupts(8)).unwrap();
+        assert!(cm33.exceptions.iter().all(|e| e.name != "SecureFault"));
+        assert!(cm33.reserved.contains(&7));
+        let cm33 = vector_table("cm33", true, &interrupts(8)).unwrap();
+        assert!(cm33.exceptions.iter().any(|e| e.name == "SecureFault" && e.number == 7));
+        assert!(!cm33.reserved.contains(&7));
+        let cm55 = vector_table("cm55", true, &interrupts(8)).unwrap();
+        assert!(cm55.exceptions.iter().any(|e| e.name == "SecureFault"));
+        // ARMv8-M Baseline has no SecureFault, even with a SAU
+        let cm23 = vector_table("cm23", true, &interrupts(8)).unwrap();
+        assert_eq!(exception_numbers(&cm23), [2, 3, 11, 14, 15]);
+
+        // Gaps in the device interrupts are reserved
+        let mut irqs = interrupts(4);
+        irqs.remove(2);
+        assert!(vector_table("cm4", false, &irqs).unwrap().reserved.contains(&18));
+
+        // Only known Cortex-M cores get a table
+        assert!(vector_table("ca7", false, &interrupts(8)).is_none());
+        assert!(vector_table("cm7s", false, &interrupts(8)).is_none());
+    }
+
+    #[test]
+    fn test_exti_lines() {
+        let lines = |irq: &str| exti_lines(irq).map(|l| (l.first, l.last));
+
+        assert_eq!(lines("EXTI0"), Some((0, 0)));
+        assert_eq!(lines("EXTI2_TSC"), Some((2, 2)));
+        assert_eq!(lines("EXTI9_5"), Some((5, 9)));
+        assert_eq!(lines("EXTI15_10"), Some((10, 15)));
+        assert_eq!(lines("EXTI4_15"), Some((4, 15)));
+        assert_eq!(lines("EXTI"), None);
+        assert_eq!(lines("PVD_EXTI16"), None);
+    }
+
+    #[test]
+    fn test_shared_exti_lines() {
+        let shared = SharedExtiLines::load(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/exti_lines.yaml")).unwrap();
+        let lines = |chip: &str, peripheral: &str, signal: &str| {
+            shared.get(chip, peripheral, signal).map(|l| (l.first, l.last))
+        };
+
+        // L4 raises PVD, RTC and COMP interrupts through EXTI lines above the GPIO ones
+        assert_eq!(lines("STM32L476RG", "PWR", "PVD"), Some((16, 16)));
+        assert_eq!(lines("STM32L476RG", "RTC", "WKUP"), Some((20, 20)));
+        assert_eq!(lines("STM32L476RG", "COMP2", "WKUP"), Some((22, 22)));
+        // G4 COMP3 and up sit past the peripheral wakeup lines
+        assert_eq!(lines("STM32G474RE", "COMP3", "WKUP"), Some((29, 29)));
+        // H7 PVD and AVD share a line, H7R/S has no rules
+        assert_eq!(lines("STM32H743ZI", "PWR", "AVD"), Some((16, 16)));
+        assert_eq!(lines("STM32H7S3L8", "PWR", "PVD"), None);
+        // Patterns are anchored
+        assert_eq!(lines("STM32L476RG", "COMP1", "WKUP2"), None);
+        assert_eq!(lines("STM32L476RG", "USART1", "GLOBAL"), None);
+    }
+}
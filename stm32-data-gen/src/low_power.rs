@@ -1,72 +1,51 @@
-use stm32_data_serde::chip::core::peripheral::rcc::StopMode;
-
-use crate::util::new_regex_map;
+use anyhow::Context;
+use serde::Deserialize;
+use stm32_data_serde::chip::LowPowerMode;
+use stm32_data_serde::chip::core::peripheral::rcc::{LowPower, StopMode};
+
+use crate::util::{RuleSource, new_regex_map};
+
+/// A rule of `data/stop_modes.yaml`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StopModeRule {
+    /// Regex on `chip:peripheral`
+    #[serde(rename = "match")]
+    pattern: String,
+    pub stop_mode: StopMode,
+    pub source: RuleSource,
+}
 
 pub struct ChipStopModes {
-    map: regex_map::RegexMap<StopMode>,
+    map: regex_map::RegexMap<StopModeRule>,
 }
 
 impl ChipStopModes {
-    pub fn new() -> Self {
-        /// Regexmap where the key is mcu_name:peripheral and the value is the stop mode.
-        /// Example: STM32WB55RG:USART1 -> StopMode::Stop2
-        #[rustfmt::skip]
-        const STOP_MODE_OVERRIDE_RULES: &[(&str, StopMode)]= &[
-            (r"^STM32WB55.*:LPTIM1", StopMode::Standby),
-            (r"^STM32WB55.*:USART1", StopMode::Stop2),
-            (r"^STM32WB55.*:LPUART1", StopMode::Standby),
-            (r"^STM32WB55.*:I2C1", StopMode::Stop2),
-            (r"^STM32WB55.*:I2C3", StopMode::Standby),
-            (r"^STM32WLE5.*:LPUART1", StopMode::Standby),
-            (r"^STM32WLE5.*:I2C1", StopMode::Stop2),
-            (r"^STM32WLE5.*:I2C2", StopMode::Stop2),
-            (r"^STM32WLE5.*:I2C3", StopMode::Standby),
-            (r"^STM32WLE5.*:LPTIM1", StopMode::Standby),
-            (r"^STM32WLE5.*:SUBGHZSPI", StopMode::Stop2),
-            (r"^STM32WLE5.*:ADC1", StopMode::Stop2),
-
-            (r"^STM32WL55.*:LPUART1", StopMode::Standby),
-            (r"^STM32WL55.*:I2C1", StopMode::Stop2),
-            (r"^STM32WL55.*:I2C2", StopMode::Stop2),
-            (r"^STM32WL55.*:I2C3", StopMode::Standby),
-            (r"^STM32WL55.*:LPTIM1", StopMode::Standby),
-            (r"^STM32WL55.*:SUBGHZSPI", StopMode::Stop2),
-            (r"^STM32WL55.*:ADC1", StopMode::Stop2),
-            (r"^STM32WL55.*:IPCC", StopMode::Standby),
-            (r"^STM32WL55.*:HSEM", StopMode::Stop2),
-
-            // STM32WBA series - from RM0515 Table 90
-            // Stop2-capable (Standby in StopMode terms): overrides generic .*:LP.* rule for LPUART1/LPTIM1
-            (r"^STM32WBA.*:LPUART1", StopMode::Standby),
-            (r"^STM32WBA.*:LPTIM1",  StopMode::Standby),
-            (r"^STM32WBA.*:I2C3",    StopMode::Standby),
-            (r"^STM32WBA.*:SPI3",    StopMode::Standby),
-            // Stop1-capable only: USART1/2/3, I2C1, SPI1, ADC4, COMP1/2
-            (r"^STM32WBA.*:USART\d", StopMode::Stop2),
-            (r"^STM32WBA.*:I2C\d",   StopMode::Stop2),
-            (r"^STM32WBA.*:SPI\d",   StopMode::Stop2),
-            (r"^STM32WBA.*:ADC4",    StopMode::Stop2),
-            (r"^STM32WBA.*:COMP\d",  StopMode::Stop2),
-            // GPDMA1 is Stop0-only (BAM wakeup) — default Stop1 is correct, no entry needed
-
-            (r"^STM32U3.*:GPDMA.*", StopMode::Stop2),
-            (r"^STM32U3.*:LPDMA.*", StopMode::Standby),
-            (r"^STM32U5.*:GPDMA.*", StopMode::Stop2),
-            (r"^STM32U5.*:LPDMA.*", StopMode::Standby),
-
-            // STM32U0 series - from RM0503 Table 22: all LPTIMs functional in Stop 2
-            (r"^STM32U0.*:LPTIM\d", StopMode::Standby),
-
-            // __ATTENTION__: Keep these rules at the bottom to grant precedence to the more specific rules above
-            // Every peripheral with LP prefix is assumed to be able enter up to Stop1 mode
-            (r".*:LP.*", StopMode::Stop2),
-            // The RTC peripheral is assumed to be able to enter up to Stop2 mode
-            (r".*:RTC", StopMode::Standby),
-        ];
-
-        Self {
-            map: new_regex_map(STOP_MODE_OVERRIDE_RULES.iter().map(|(k, v)| (*k, v.clone()))),
+    /// Load the stop mode overrides of peripherals from `data/stop_modes.yaml`. The first matching rule wins.
+    pub fn parse() -> anyhow::Result<Self> {
+        Self::load("data/stop_modes.yaml")
+    }
+
+    fn load(path: &str) -> anyhow::Result<Self> {
+        let rules: Vec<StopModeRule> = serde_yaml::from_str(&std::fs::read_to_string(path).context(path.to_string())?)
+            .context(path.to_string())?;
+
+        for rule in &rules {
+            regex::Regex::new(&rule.pattern).with_context(|| format!("{}: {}", path, rule.pattern))?;
+            rule.source
+                .validate()
+                .with_context(|| format!("{}: {}", path, rule.pattern))?;
         }
+        RuleSource::check_unverified(path, rules.iter().map(|r| &r.source))?;
+
+        Ok(Self {
+            map: new_regex_map(rules.into_iter().map(|r| (r.pattern.clone(), r))),
+        })
+    }
+
+    /// Get the rule that sets the stop mode limit of a peripheral, based on the MCU and peripheral name.
+    pub(crate) fn peripheral_stop_mode_rule(&self, mcu_name: &str, peripheral: &str) -> Option<&StopModeRule> {
+        self.map.get(&format!("{mcu_name}:{peripheral}")).next()
     }
 
     /// Get the stop mode limit for a peripheral based on the MCU and peripheral name.
@@ -75,8 +54,81 @@ impl ChipStopModes {
     /// Parameters:
     /// - mcu_name: the full name of the MCU (e.g., "STM32WB55RG")
     /// - peripheral: the name of the peripheral (e.g., "USART1")
+    #[cfg(test)]
     pub(crate) fn peripheral_stop_mode_info(&self, mcu_name: &str, peripheral: &str) -> Option<StopMode> {
-        self.map.get(&format!("{mcu_name}:{peripheral}")).next().cloned()
+        self.peripheral_stop_mode_rule(mcu_name, peripheral)
+            .map(|r| r.stop_mode.clone())
+    }
+}
+
+/// A rule of `data/low_power.yaml`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LowPowerRule {
+    /// Regex on `chip:peripheral`
+    #[serde(rename = "match")]
+    pattern: String,
+    #[serde(default)]
+    functional_in: Option<LowPowerMode>,
+    #[serde(default)]
+    wakeup: bool,
+    #[serde(default)]
+    retention: bool,
+    #[serde(default)]
+    backup_domain: bool,
+    source: RuleSource,
+}
+
+pub struct PeripheralLowPowers {
+    map: regex_map::RegexMap<(LowPower, RuleSource)>,
+}
+
+impl PeripheralLowPowers {
+    /// Load the low-power behavior of peripherals from `data/low_power.yaml`. The first matching rule wins.
+    pub fn parse() -> anyhow::Result<Self> {
+        Self::load("data/low_power.yaml")
+    }
+
+    fn load(path: &str) -> anyhow::Result<Self> {
+        let rules: Vec<LowPowerRule> = serde_yaml::from_str(&std::fs::read_to_string(path).context(path.to_string())?)
+            .context(path.to_string())?;
+
+        for rule in &rules {
+            regex::Regex::new(&rule.pattern).with_context(|| format!("{}: {}", path, rule.pattern))?;
+            if rule.wakeup && rule.functional_in.is_none() {
+                anyhow::bail!(
+                    "{}: {} can wake the chip up but is not functional in any low-power mode",
+                    path,
+                    rule.pattern
+                );
+            }
+            rule.source
+                .validate()
+                .with_context(|| format!("{}: {}", path, rule.pattern))?;
+        }
+        RuleSource::check_unverified(path, rules.iter().map(|r| &r.source))?;
+
+        Ok(Self {
+            map: new_regex_map(rules.into_iter().map(|r| {
+                (
+                    r.pattern,
+                    (
+                        LowPower {
+                            functional_in: r.functional_in,
+                            wakeup: r.wakeup,
+                            retention: r.retention,
+                            backup_domain: r.backup_domain,
+                        },
+                        r.source,
+                    ),
+                )
+            })),
+        })
+    }
+
+    /// Get the low-power behavior of a peripheral and the source of the rule it comes from.
+    pub fn get(&self, mcu_name: &str, peripheral: &str) -> Option<&(LowPower, RuleSource)> {
+        self.map.get(&format!("{mcu_name}:{peripheral}")).next()
     }
 }
 
@@ -86,7 +138,8 @@ mod tests {
 
     #[test]
     fn test_get_peripheral_stop_mode_info() {
-        let chip_stop_modes = ChipStopModes::new();
+        let chip_stop_modes =
+            ChipStopModes::load(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/stop_modes.yaml")).unwrap();
 
         // MCU independent rule for RTC
         assert_eq!(
@@ -115,4 +168,31 @@ mod tests {
             Some(StopMode::Standby)
         );
     }
+
+    #[test]
+    fn test_get_peripheral_low_power() {
+        let low_powers =
+            PeripheralLowPowers::load(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/low_power.yaml")).unwrap();
+        let functional_in = |chip: &str, peripheral: &str| {
+            low_powers
+                .get(chip, peripheral)
+                .and_then(|(l, _)| l.functional_in.clone())
+        };
+
+        // The backup domain of WB keeps running in Shutdown, WBA has no Shutdown mode
+        assert_eq!(functional_in("STM32WB55RG", "RTC"), Some(LowPowerMode::Shutdown));
+        assert_eq!(functional_in("STM32WBA52CG", "RTC"), Some(LowPowerMode::Standby));
+        assert_eq!(functional_in("STM32F429ZI", "RTC"), Some(LowPowerMode::Standby));
+
+        assert_eq!(functional_in("STM32L476RG", "LPUART1"), Some(LowPowerMode::Stop2));
+        assert_eq!(functional_in("STM32L476RG", "USART2"), Some(LowPowerMode::Stop1));
+        assert_eq!(functional_in("STM32L476RG", "SPI1"), None);
+
+        let (bkp, source) = low_powers.get("STM32F103C8", "BKP").unwrap();
+        assert!(bkp.backup_domain && bkp.retention && bkp.functional_in.is_none());
+        assert_eq!(source.document, "heuristic");
+
+        let (_, source) = low_powers.get("STM32L476RG", "LPUART1").unwrap();
+        assert_eq!(source.document, "RM0351, RM0432");
+    }
 }
@@ -4,8 +4,11 @@ use clap::{Parser, ValueEnum};
 use env_logger::Env;
 use log::{LevelFilter, info};
 
+mod boot;
+mod capability;
 mod check;
 mod chips;
+mod dbgmcu;
 mod dma;
 mod docs;
 mod generator;
@@ -14,11 +17,14 @@ mod header;
 mod interrupts;
 mod low_power;
 mod memory;
+mod multicore;
 mod normalize_peris;
 mod package;
 mod perimap;
 mod rcc;
 mod registers;
+mod remap;
+mod security;
 mod trigger;
 mod util;
 
@@ -127,8 +133,8 @@ fn main() -> anyhow::Result<()> {
     let mut stopwatch = Stopwatch::new();
 
     let perimap = perimap::Perimap::new();
-    let stop_modes = low_power::ChipStopModes::new();
-    let triggers = trigger::Triggers::new();
+    let stop_modes = low_power::ChipStopModes::parse()?;
+    let low_powers = low_power::PeripheralLowPowers::parse()?;
     let chip_memories = memory::ChipMemories::new();
 
     stopwatch.section("Removing build directory");
@@ -149,12 +155,32 @@ fn main() -> anyhow::Result<()> {
 
     // stopwatch.section("Parsing RCC registers");
     let peripheral_to_clock = rcc::ParsedRccs::parse(&registers)?;
+    peripheral_to_clock.write()?;
+
+    // stopwatch.section("Parsing DBGMCU registers");
+    let debug_freeze = dbgmcu::ParsedDbgmcus::parse(&registers)?;
+
+    // stopwatch.section("Parsing GTZC/RIFSC registers");
+    let security = security::ParsedSecurity::parse(&registers)?;
+
+    // stopwatch.section("Parsing capabilities");
+    let capabilities = capability::ParsedCapabilities::parse(&registers)?;
+
+    // stopwatch.section("Parsing boot and wakeup pin registers");
+    let boot = boot::ParsedBoot::parse(&registers)?;
+
+    // stopwatch.section("Parsing triggers");
+    let triggers = trigger::Triggers::parse(&registers)?;
+    let internal_triggers = trigger::InternalTriggers::parse()?;
+
+    // stopwatch.section("Parsing AFIO/SYSCFG remaps");
+    let remaps = remap::Remaps::parse(&registers)?;
 
     // stopwatch.section("Parsing docs");
     let docs = docs::Docs::parse()?;
 
     // stopwatch.section("Parsing DMA");
-    let mut dma_channels = dma::DmaChannels::parse(&perimap)?;
+    let mut dma_channels = dma::DmaChannels::parse(&registers)?;
 
     // stopwatch.section("Parsing GPIO AF");
     let mut af = gpio_af::Af::parse()?;
@@ -180,11 +206,18 @@ fn main() -> anyhow::Result<()> {
         af,
         perimap,
         stop_modes,
+        low_powers,
         triggers,
+        internal_triggers,
+        remaps,
         chip_memories,
         registers.blocks,
         chip_interrupts,
         peripheral_to_clock,
+        debug_freeze,
+        security,
+        capabilities,
+        boot,
         dma_channels,
         chips,
         docs,
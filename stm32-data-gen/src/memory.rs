@@ -1,5 +1,8 @@
-use stm32_data_serde::chip::Memory;
-use stm32_data_serde::chip::memory::{self, Access, Settings};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use stm32_data_serde::chip::memory::{self, Access, Attributes, Protection, Retention, Settings};
+use stm32_data_serde::chip::{Flash, Memory, flash};
 
 use crate::util::new_regex_map;
 
@@ -241,11 +244,11 @@ const MEMS: &[(&str, &[&[Mem]])] = &[
                                         mem!(BANK_1 { 0x08000000 256 }, BANK_2 { 0x08040000 256 }, CCMRAM_ICODE { 0x10000000 32 }, SRAM1 { 0x20000000 80 }, SRAM2 { 0x20014000 16 }, CCMRAM_DCODE { 0x20018000 32 })
                                       ]),
     // H5
-    ("STM32H5...B",                  &[mem!(BANK_1 { 0x08000000 64 },   BANK_2 { 0x08010000 64 },   SRAM1 { 0x20000000 16 },  SRAM2 { 0x20004000 16 },                           BKPSRAM { 0x40036400 2 }, OTP { 0x08fff000 2 }, FMC_BANK_1 { 0x70000000 256000}, FMC_BANK_3 {  0x7fffffff 256000 }, SDRAM_BANK_1 { 0xc0000000 256000}, SDRAM_BANK_2 { 0xd0000000 256000}, OCTOSPI_BANK_1 { 0xa0000000 256000})]),
-    ("STM32H5...C",                  &[mem!(BANK_1 { 0x08000000 128 },  BANK_2 { 0x08020000 128 },  SRAM1 { 0x20000000 128 }, SRAM2 { 0x20020000 80 }, SRAM3 { 0x20034000 64 },  BKPSRAM { 0x40036400 2 }, OTP { 0x08fff000 2 }, FMC_BANK_1 { 0x70000000 256000}, FMC_BANK_3 {  0x7fffffff 256000 }, SDRAM_BANK_1 { 0xc0000000 256000}, SDRAM_BANK_2 { 0xd0000000 256000}, OCTOSPI_BANK_1 { 0xa0000000 256000})]),
-    ("STM32H5...E",                  &[mem!(BANK_1 { 0x08000000 256 },  BANK_2 { 0x08040000 256 },  SRAM1 { 0x20000000 128 }, SRAM2 { 0x20020000 80 }, SRAM3 { 0x20034000 64 },  BKPSRAM { 0x40036400 2 }, OTP { 0x08fff000 2 }, FMC_BANK_1 { 0x70000000 256000}, FMC_BANK_3 {  0x7fffffff 256000 }, SDRAM_BANK_1 { 0xc0000000 256000}, SDRAM_BANK_2 { 0xd0000000 256000}, OCTOSPI_BANK_1 { 0xa0000000 256000})]),
-    ("STM32H5...G",                  &[mem!(BANK_1 { 0x08000000 512 },  BANK_2 { 0x08080000 512 },  SRAM1 { 0x20000000 256 }, SRAM2 { 0x20040000 64 }, SRAM3 { 0x20050000 320 }, BKPSRAM { 0x40036400 4 }, OTP { 0x08fff000 2 }, FMC_BANK_1 { 0x70000000 256000}, FMC_BANK_3 {  0x7fffffff 256000 }, SDRAM_BANK_1 { 0xc0000000 256000}, SDRAM_BANK_2 { 0xd0000000 256000}, OCTOSPI_BANK_1 { 0xa0000000 256000})]),
-    ("STM32H5...I",                  &[mem!(BANK_1 { 0x08000000 1024 }, BANK_2 { 0x08100000 1024 }, SRAM1 { 0x20000000 256 }, SRAM2 { 0x20040000 64 }, SRAM3 { 0x20050000 320 }, BKPSRAM { 0x40036400 4 }, OTP { 0x08fff000 2 }, FMC_BANK_1 { 0x70000000 256000}, FMC_BANK_3 {  0x7fffffff 256000 }, SDRAM_BANK_1 { 0xc0000000 256000}, SDRAM_BANK_2 { 0xd0000000 256000}, OCTOSPI_BANK_1 { 0xa0000000 256000})]),
+    ("STM32H5...B",                  &[mem!(BANK_1 { 0x08000000 64 },   BANK_2 { 0x08010000 64 },   SRAM1 { 0x20000000 16 },  SRAM2 { 0x20004000 16 },                           BKPSRAM { 0x40036400 2 }, OTP { 0x08fff000 2 })]),
+    ("STM32H5...C",                  &[mem!(BANK_1 { 0x08000000 128 },  BANK_2 { 0x08020000 128 },  SRAM1 { 0x20000000 128 }, SRAM2 { 0x20020000 80 }, SRAM3 { 0x20034000 64 },  BKPSRAM { 0x40036400 2 }, OTP { 0x08fff000 2 })]),
+    ("STM32H5...E",                  &[mem!(BANK_1 { 0x08000000 256 },  BANK_2 { 0x08040000 256 },  SRAM1 { 0x20000000 128 }, SRAM2 { 0x20020000 80 }, SRAM3 { 0x20034000 64 },  BKPSRAM { 0x40036400 2 }, OTP { 0x08fff000 2 })]),
+    ("STM32H5...G",                  &[mem!(BANK_1 { 0x08000000 512 },  BANK_2 { 0x08080000 512 },  SRAM1 { 0x20000000 256 }, SRAM2 { 0x20040000 64 }, SRAM3 { 0x20050000 320 }, BKPSRAM { 0x40036400 4 }, OTP { 0x08fff000 2 })]),
+    ("STM32H5...I",                  &[mem!(BANK_1 { 0x08000000 1024 }, BANK_2 { 0x08100000 1024 }, SRAM1 { 0x20000000 256 }, SRAM2 { 0x20040000 64 }, SRAM3 { 0x20050000 320 }, BKPSRAM { 0x40036400 4 }, OTP { 0x08fff000 2 })]),
     // H7RS
     ("STM32H7[RS].*",                &[mem!(BANK_1 { 0x08000000 64 }, ITCM { 0x00000000 192 }, DTCM { 0x20000000 192 }, SRAM1 { 0x24000000 128 }, SRAM2 { 0x24020000 128 }, SRAM3 { 0x24040000 128 }, SRAM4 { 0x24060000 72 }, AHB_SRAM1 { 0x30000000 16 }, AHB_SRAM2 { 0x30004000 16 })]),
     // H7
@@ -490,9 +493,119 @@ const FLASH_INFO: &[(&str, &[FlashInfo])] = &[
     ("STM32.*",                 &[FlashInfo{ erase_value: 0xFF, write_size:  8, erase_size: &[(  2*1024, 0)] }]),
 ];
 
+#[derive(Clone, Copy)]
+struct BankMode {
+    register: &'static str,
+    field: &'static str,
+    /// Value of the option bit for the first memory variant
+    single: u8,
+    /// Value of the option bit for the second memory variant
+    dual: u8,
+}
+
+/// Option bit selecting between the single and dual bank layouts on chips with two memory variants.
+#[rustfmt::skip]
+const BANK_MODE: &[(&str, BankMode)] = &[
+    ("STM32F4[2367]..G",        BankMode { register: "OPTCR", field: "DB1M",      single: 0, dual: 1 }),
+    ("STM32F7[67]..[IG]",       BankMode { register: "OPTCR", field: "nDBANK",    single: 1, dual: 0 }),
+    ("STM32G0...C",             BankMode { register: "OPTR",  field: "DUAL_BANK", single: 0, dual: 1 }),
+    ("STM32G4[78].*",           BankMode { register: "OPTR",  field: "DBANK",     single: 0, dual: 1 }),
+    ("STM32L4[PQRS].*",         BankMode { register: "OPTR",  field: "DBANK",     single: 0, dual: 1 }),
+    ("STM32L5...E",             BankMode { register: "OPTR",  field: "DBANK",     single: 0, dual: 1 }),
+];
+
+/// Option bit swapping the banks at boot, for chips with more than one bank. `None` for families
+/// that have no swap bit.
+#[rustfmt::skip]
+const BANK_SWAP: &[(&str, Option<(&str, &str)>)] = &[
+    ("STM32C5.*",               Some(("OPTSR_PRG", "SWAP_BANK"))),
+    ("STM32F4[2367].*",         Some(("OPTCR",     "BFB2"))),
+    ("STM32G4.*",               Some(("OPTR",      "BFB2"))),
+    ("STM32H5.*",               Some(("OPTSR_PRG", "SWAP_BANK"))),
+    ("STM32H7[RS].*",           None),
+    ("STM32H7.*",               Some(("OPTSR_PRG", "SWAP_BANK_OPT"))),
+    ("STM32L1.*",               Some(("OBR",       "BFB2"))),
+    ("STM32L4.*",               Some(("OPTR",      "BFB2"))),
+    ("STM32L5.*",               Some(("OPTR",      "SWAP_BANK"))),
+    ("STM32U[35].*",            Some(("OPTR",      "SWAP_BANK"))),
+    ("STM32WBA.*",              Some(("OPTR",      "SWAP_BANK"))),
+];
+
+/// Chips whose flash can be read from one bank while the other bank is programmed or erased.
+/// Single-bank layouts never can. Dual-bank layouts of families not listed here are left unknown.
+const READ_WHILE_WRITE: &[&str] = &[
+    "STM32F1.*",
+    "STM32F4.*",
+    "STM32F7.*",
+    "STM32G0.*",
+    "STM32G4.*",
+    "STM32H5.*",
+    "STM32H7.*",
+    "STM32L0.*",
+    "STM32L1.*",
+    "STM32L4.*",
+    "STM32L5.*",
+    "STM32U5.*",
+    "STM32WBA.*",
+];
+
+#[derive(Clone)]
+struct MemAttributes {
+    protection: Option<Protection>,
+    retention: Option<Retention>,
+    inaccessible_by: &'static [&'static str],
+}
+
+macro_rules! attrs {
+    ($protection:expr, $retention:expr) => {
+        attrs!($protection, $retention, &[])
+    };
+    ($protection:expr, $retention:expr, $inaccessible_by:expr) => {
+        MemAttributes {
+            protection: $protection,
+            retention: $retention,
+            inaccessible_by: $inaccessible_by,
+        }
+    };
+}
+
+/// RAM attributes, keyed by `chip:memory`. The first match wins.
+/// `None` is not known: RAMs without a match have no known protection or retention, and
+/// `Some(Protection::None)` is for RAMs known to have no error detection.
+#[rustfmt::skip]
+const MEM_ATTRIBUTES: &[(&str, MemAttributes)] = &[
+    // H7RS: TCMs are only reachable by the CPU
+    ("STM32H7[RS].*:(ITCM|DTCM)",             attrs!(Some(Protection::Ecc), Some(Retention::Stop), &["GPDMA1", "HPDMA1"])),
+    ("STM32H7[RS].*:.*SRAM.*",                attrs!(Some(Protection::Ecc), Some(Retention::Stop))),
+    // H7: TCMs are reachable by the CPU and MDMA only, BDMA only reaches the D3 domain
+    ("STM32H7[AB].*:(ITCM|DTCM)",             attrs!(Some(Protection::Ecc), Some(Retention::Stop), &["DMA1", "DMA2", "BDMA1", "BDMA2"])),
+    ("STM32H7[AB].*:.*SRAM.*",                attrs!(Some(Protection::Ecc), Some(Retention::Stop))),
+    ("STM32H7.*:(ITCM|DTCM)",                 attrs!(Some(Protection::Ecc), Some(Retention::Stop), &["DMA1", "DMA2", "BDMA"])),
+    ("STM32H7.*:(AXISRAM|SRAM[123])",         attrs!(Some(Protection::Ecc), Some(Retention::Stop), &["BDMA"])),
+    ("STM32H7.*:SRAM4",                       attrs!(Some(Protection::Ecc), Some(Retention::Stop))),
+    // F3/F4: CCM RAM is only on the CPU D-bus
+    ("STM32F3.*:CCMRAM",                      attrs!(None, Some(Retention::Stop), &["DMA1", "DMA2"])),
+    ("STM32F4.*:CCMRAM",                      attrs!(Some(Protection::None), Some(Retention::Stop), &["DMA1", "DMA2"])),
+    // F1/F2/F4 have no RAM parity or ECC
+    ("STM32F[124].*:SRAM\\d?",                attrs!(Some(Protection::None), Some(Retention::Stop))),
+    ("STM32G0.*:SRAM",                        attrs!(Some(Protection::Parity), Some(Retention::Stop))),
+    ("STM32G4.*:CCMRAM_(ICODE|DCODE)",        attrs!(Some(Protection::Parity), Some(Retention::Stop))),
+    ("STM32G4.*:SRAM2",                       attrs!(None, Some(Retention::Standby))),
+    ("STM32L4.*:SRAM2(_ICODE)?",              attrs!(Some(Protection::Parity), Some(Retention::Standby))),
+    ("STM32U5.*:SRAM2",                       attrs!(Some(Protection::Ecc), Some(Retention::Standby))),
+    ("STM32WBA.*:SRAM2",                      attrs!(Some(Protection::Parity), Some(Retention::Standby))),
+    ("STM32WB.*:SRAM2A(_ICODE)?",             attrs!(None, Some(Retention::Standby))),
+    ("STM32H5.*:BKPSRAM",                     attrs!(Some(Protection::Ecc), Some(Retention::Backup))),
+    ("STM32.*:BKPSRAM",                       attrs!(None, Some(Retention::Backup))),
+];
+
 pub struct ChipMemories {
     mems: regex_map::RegexMap<Vec<Vec<Mem>>>,
     flash_info: regex_map::RegexMap<Vec<FlashInfo>>,
+    attributes: regex_map::RegexMap<MemAttributes>,
+    bank_mode: regex_map::RegexMap<BankMode>,
+    bank_swap: regex_map::RegexMap<Option<(&'static str, &'static str)>>,
+    read_while_write: regex_map::RegexMap<()>,
 }
 
 impl ChipMemories {
@@ -500,20 +613,17 @@ impl ChipMemories {
         Self {
             mems: new_regex_map(MEMS.iter().map(|(k, v)| (k, v.iter().map(|x| x.to_vec()).collect()))),
             flash_info: new_regex_map(FLASH_INFO.iter().map(|(k, v)| (k, v.to_vec()))),
+            attributes: new_regex_map(MEM_ATTRIBUTES.iter().map(|(k, v)| (k, v.clone()))),
+            bank_mode: new_regex_map(BANK_MODE.iter().copied()),
+            bank_swap: new_regex_map(BANK_SWAP.iter().copied()),
+            read_while_write: new_regex_map(READ_WHILE_WRITE.iter().map(|&k| (k, ()))),
         }
     }
 
-    pub fn get(&self, chip: &str) -> Vec<Vec<Memory>> {
-        let mems_variations = self.mems.get(chip).next().unwrap();
-        let flash_variations = self.flash_info.get(chip).next().unwrap();
-
-        assert_eq!(
-            mems_variations.len(),
-            flash_variations.len(),
-            "All memory variants must be present in both the mems and the flash info: {chip}"
-        );
+    pub fn get(&self, chip: &str) -> anyhow::Result<Vec<Vec<Memory>>> {
+        let (mems_variations, flash_variations) = self.variations(chip)?;
 
-        mems_variations
+        Ok(mems_variations
             .into_iter()
             .zip(flash_variations.into_iter())
             .map(|(mems, flash)| {
@@ -525,6 +635,7 @@ impl ChipMemories {
                             res.push(Memory {
                                 name: mem.name.to_string(),
                                 address: mem.address,
+                                secure_address: None,
                                 size: mem.size,
                                 kind: memory::Kind::Flash,
                                 settings: Some(Settings {
@@ -533,6 +644,8 @@ impl ChipMemories {
                                     erase_value: flash.erase_value,
                                 }),
                                 access: mem.access,
+                                attributes: Attributes::default(),
+                                peripheral: None,
                             });
                         } else {
                             let mut offs = 0;
@@ -549,6 +662,7 @@ impl ChipMemories {
                                 res.push(Memory {
                                     name: format!("{}_REGION_{}", mem.name, i + 1),
                                     address: mem.address + offs,
+                                    secure_address: None,
                                     size: size,
                                     kind: memory::Kind::Flash,
                                     settings: Some(Settings {
@@ -557,6 +671,8 @@ impl ChipMemories {
                                         erase_value: flash.erase_value,
                                     }),
                                     access: mem.access,
+                                    attributes: Attributes::default(),
+                                    peripheral: None,
                                 });
                                 offs += size;
                             }
@@ -565,23 +681,29 @@ impl ChipMemories {
                         res.push(Memory {
                             name: mem.name.to_string(),
                             address: mem.address,
+                            secure_address: None,
                             size: mem.size,
-                            kind: memory::Kind::Flash,
+                            kind: memory::Kind::Otp,
                             settings: Some(Settings {
                                 write_size: flash.write_size,
                                 erase_size: 0,
                                 erase_value: flash.erase_value,
                             }),
                             access: mem.access,
+                            attributes: Attributes::default(),
+                            peripheral: None,
                         });
                     } else if mem.name.starts_with("EEPROM") {
                         res.push(Memory {
                             name: mem.name.to_string(),
                             address: mem.address,
+                            secure_address: None,
                             size: mem.size,
                             kind: memory::Kind::Eeprom,
                             settings: None,
                             access: mem.access,
+                            attributes: Attributes::default(),
+                            peripheral: None,
                         });
                     } else {
                         let mut kind = memory::Kind::Ram;
@@ -591,18 +713,454 @@ impl ChipMemories {
                         res.push(Memory {
                             name: mem.name.to_string(),
                             address: mem.address,
+                            secure_address: None,
                             size: mem.size,
                             kind,
                             settings: None,
                             access: mem.access,
+                            attributes: Attributes::default(),
+                            peripheral: None,
                         });
                     }
                 }
 
                 res.sort_by_key(|m| (m.address, m.name.clone()));
 
+                self.apply_attributes(chip, &mut res);
+
                 res
             })
-            .collect()
+            .collect())
+    }
+
+    /// Get the memory and flash info variants of a chip, which must come in the same number.
+    fn variations(&self, chip: &str) -> anyhow::Result<(&Vec<Vec<Mem>>, &Vec<FlashInfo>)> {
+        let Some(mems_variations) = self.mems.get(chip).next() else {
+            anyhow::bail!("{chip}: no memory table");
+        };
+        let Some(flash_variations) = self.flash_info.get(chip).next() else {
+            anyhow::bail!("{chip}: no flash info");
+        };
+        if mems_variations.len() != flash_variations.len() {
+            anyhow::bail!(
+                "{}: {} memory variants but {} flash info variants, all variants must be in both",
+                chip,
+                mems_variations.len(),
+                flash_variations.len()
+            );
+        }
+        Ok((mems_variations, flash_variations))
+    }
+
+    /// Get the flash layout of each memory variant of the chip, in the same order as [`Self::get`].
+    ///
+    /// Banks are split into sectors following the erase sizes of the chip. Sector numbering restarts
+    /// in each bank, except on F1 where pages are numbered across banks, on G0 where the second bank
+    /// starts at page 256 and on F2/F4/F7 where it starts at sector 12.
+    pub fn flash(&self, chip: &str) -> anyhow::Result<Vec<Flash>> {
+        let (mems_variations, flash_variations) = self.variations(chip)?;
+        let bank_mode = self.bank_mode.get(chip).next();
+        let bank_swap = self.bank_swap.get(chip).next().copied().flatten();
+        let read_while_write = self.read_while_write.get(chip).next().is_some().then_some(true);
+
+        let mut res: Vec<Flash> = mems_variations
+            .iter()
+            .zip(flash_variations.iter())
+            .map(|(mems, flash)| {
+                let mut index = 0;
+                let mut banks = Vec::new();
+                for (n, mem) in mems.iter().filter(|m| m.name.starts_with("BANK")).enumerate() {
+                    if n > 0 && flash.erase_size.len() > 1 {
+                        index = 12 * n as u32;
+                    } else if n > 0 && chip.starts_with("STM32G0") {
+                        index = 256 * n as u32;
+                    } else if n > 0 && !chip.starts_with("STM32F1") {
+                        index = 0;
+                    }
+
+                    let mut sectors = Vec::new();
+                    let mut offset = 0;
+                    for (i, &(erase_size, count)) in flash.erase_size.iter().enumerate() {
+                        let last = i == flash.erase_size.len() - 1 || count == 0;
+                        let mut done = 0;
+                        while offset < mem.size && (last || done < count) {
+                            sectors.push(flash::Sector {
+                                index,
+                                offset,
+                                size: erase_size,
+                            });
+                            index += 1;
+                            offset += erase_size;
+                            done += 1;
+                        }
+                    }
+
+                    banks.push(flash::Bank {
+                        name: mem.name.to_string(),
+                        address: mem.address,
+                        size: mem.size,
+                        sectors,
+                    });
+                }
+
+                let dual_bank = banks.len() > 1;
+                Flash {
+                    banks,
+                    write_size: flash.write_size,
+                    erase_value: flash.erase_value,
+                    bank_mode: None,
+                    bank_swap: bank_swap
+                        .filter(|_| dual_bank)
+                        .map(|(register, field)| flash::OptionBit {
+                            register: register.to_string(),
+                            field: field.to_string(),
+                        }),
+                    read_while_write: if dual_bank { read_while_write } else { Some(false) },
+                }
+            })
+            .collect();
+
+        if let Some(mode) = bank_mode {
+            if res.len() != 2 {
+                anyhow::bail!(
+                    "{}: bank mode {}.{} needs a single and a dual bank memory variant, found {}",
+                    chip,
+                    mode.register,
+                    mode.field,
+                    res.len()
+                );
+            }
+            for (flash, value) in res.iter_mut().zip([mode.single, mode.dual]) {
+                flash.bank_mode = Some(flash::BankMode {
+                    register: mode.register.to_string(),
+                    field: mode.field.to_string(),
+                    value,
+                });
+            }
+        }
+
+        res.retain(|f| !f.banks.is_empty());
+        Ok(res)
+    }
+
+    /// Fill in the attributes of the RAM regions.
+    ///
+    /// Regions that are the same memory seen from another bus (`SRAM2` and `SRAM2_ICODE`,
+    /// `CCMRAM_ICODE` and `CCMRAM_DCODE`) list each other's address as an alias.
+    fn apply_attributes(&self, chip: &str, memories: &mut [Memory]) {
+        let base_name = |name: &str| {
+            let name = name
+                .strip_suffix("_ICODE")
+                .or(name.strip_suffix("_DCODE"))
+                .unwrap_or(name);
+            name.to_string()
+        };
+        let regions: Vec<(String, u32, u32)> = memories
+            .iter()
+            .filter(|m| m.kind == memory::Kind::Ram)
+            .map(|m| (base_name(&m.name), m.address, m.size))
+            .collect();
+
+        for mem in memories.iter_mut().filter(|m| m.kind == memory::Kind::Ram) {
+            let attributes = self.attributes.get(&format!("{chip}:{}", mem.name)).next();
+
+            let name = base_name(&mem.name);
+            mem.attributes = Attributes {
+                protection: attributes.and_then(|a| a.protection),
+                retention: attributes.and_then(|a| a.retention),
+                inaccessible_by: attributes
+                    .map(|a| a.inaccessible_by.iter().map(|s| s.to_string()).collect())
+                    .unwrap_or_default(),
+                aliases: regions
+                    .iter()
+                    .filter(|(n, address, size)| *n == name && *address != mem.address && *size == mem.size)
+                    .map(|&(_, address, _)| address)
+                    .collect(),
+            };
+        }
+    }
+}
+
+/// Fill in the secure aliases of flash, SRAM and backup SRAM on TrustZone chips.
+///
+/// The memory tables above only list the non-secure addresses. The headers define the base of
+/// each non-secure/secure alias pair, and every region inside a non-secure window gets the same
+/// offset applied. The OTP area, option bytes, system memory and external windows have no secure alias.
+pub fn apply_secure_aliases(memories: &mut [Vec<Memory>], defines: &crate::header::Defines) {
+    const ALIASES: &[(&str, &str)] = &[
+        ("FLASH_BASE_NS", "FLASH_BASE_S"),
+        ("SRAM1_BASE_NS", "SRAM1_BASE_S"),
+        ("PERIPH_BASE_NS", "PERIPH_BASE_S"),
+    ];
+
+    let windows: Vec<(u32, u32)> = ALIASES
+        .iter()
+        .filter_map(|(ns, s)| {
+            let ns = u32::try_from(*defines.0.get(*ns)?).ok()?;
+            let s = u32::try_from(*defines.0.get(*s)?).ok()?;
+            (s > ns).then_some((ns, s))
+        })
+        .collect();
+
+    for mem in memories.iter_mut().flatten() {
+        if mem.kind != memory::Kind::Flash && mem.kind != memory::Kind::Ram {
+            continue;
+        }
+        mem.secure_address = windows
+            .iter()
+            .find(|&&(ns, s)| mem.address >= ns && mem.address < s)
+            .map(|&(ns, s)| mem.address + (s - ns));
+    }
+}
+
+/// Add the memory-mapped windows of the external memory controllers present on the chip.
+///
+/// The window base addresses come from the headers, the window sizes from the memory map of the
+/// reference manual of each family. `peripherals` holds the name and register address of every
+/// peripheral of the chip; a window is only added if its owner exists, the define is not the
+/// register block itself and it doesn't overlap a region already listed. Families missing from
+/// the table below get no windows.
+pub fn add_external_windows(
+    chip: &str,
+    memories: &mut [Vec<Memory>],
+    defines: &crate::header::Defines,
+    peripherals: &HashMap<String, u32>,
+) {
+    // (define, peripheral)
+    #[rustfmt::skip]
+    const DEFINES: &[(&str, &str)] = &[
+        ("FMC_BANK1",     "FMC"),
+        ("FMC_BANK2",     "FMC"),
+        ("FMC_BANK3",     "FMC"),
+        ("FMC_BANK4",     "FMC"),
+        ("FMC_BANK5_6",   "FMC"),
+        ("FSMC_BANK1",    "FSMC"),
+        ("FSMC_BANK2",    "FSMC"),
+        ("FSMC_BANK3",    "FSMC"),
+        ("FSMC_BANK4",    "FSMC"),
+        ("QSPI_BASE",     "QUADSPI"),
+        ("OCTOSPI1_BASE", "OCTOSPI1"),
+        ("OCTOSPI2_BASE", "OCTOSPI2"),
+        ("HSPI1_BASE",    "HSPI1"),
+        ("XSPI1_BASE",    "XSPI1"),
+        ("XSPI2_BASE",    "XSPI2"),
+        ("XSPI3_BASE",    "XSPI3"),
+    ];
+
+    // Windows starting at each define, keyed by `chip:define`, as (memory name, size) laid out
+    // back to back. The first match wins.
+    #[rustfmt::skip]
+    const WINDOWS: &[(&str, &[(&str, u32)])] = &[
+        // RM0008, RM0033, RM0090, RM0038: four 256 Mbyte FSMC banks (bank 1 only on L1)
+        ("STM32(F[124]|L1).*:FSMC_BANK1",   &[("FSMC_BANK_1", 0x1000_0000)]),
+        ("STM32F[124].*:FSMC_BANK2",        &[("FSMC_BANK_2", 0x1000_0000)]),
+        ("STM32F[124].*:FSMC_BANK3",        &[("FSMC_BANK_3", 0x1000_0000)]),
+        ("STM32F[124].*:FSMC_BANK4",        &[("FSMC_BANK_4", 0x1000_0000)]),
+        // RM0316, RM0090, RM0385, RM0410, RM0440, RM0481, RM0433, RM0455, RM0351, RM0432, RM0456:
+        // 256 Mbyte FMC banks, the SDRAM banks 1 and 2 follow each other from 0xC000_0000
+        ("STM32(F[3467]|G4|H[57]|L4|U5).*:FMC_BANK1",   &[("FMC_BANK_1", 0x1000_0000)]),
+        ("STM32(F[3467]|G4|H[57]|L4|U5).*:FMC_BANK2",   &[("FMC_BANK_2", 0x1000_0000)]),
+        ("STM32(F[3467]|G4|H[57]|L4|U5).*:FMC_BANK3",   &[("FMC_BANK_3", 0x1000_0000)]),
+        ("STM32(F[3467]|G4|H[57]|L4|U5).*:FMC_BANK4",   &[("FMC_BANK_4", 0x1000_0000)]),
+        ("STM32(F[467]|H[57]).*:FMC_BANK5_6",           &[("SDRAM_BANK_1", 0x1000_0000), ("SDRAM_BANK_2", 0x1000_0000)]),
+        // RM0390, RM0402, RM0430, RM0385, RM0410, RM0440, RM0433, RM0351: 256 Mbyte QUADSPI window
+        ("STM32(F[467]|G4|H7|L4).*:QSPI_BASE",          &[("QUADSPI", 0x1000_0000)]),
+        // RM0481: 256 Mbyte OCTOSPI window, named as in the former H5 memory table
+        ("STM32H5.*:OCTOSPI1_BASE",                     &[("OCTOSPI_BANK_1", 0x1000_0000)]),
+        // RM0432, RM0438, RM0456, RM0455: 256 Mbyte OCTOSPI windows
+        ("STM32(H7|L4|L5|U5).*:OCTOSPI1_BASE",          &[("OCTOSPI1_WINDOW", 0x1000_0000)]),
+        ("STM32(H7|L4|L5|U5).*:OCTOSPI2_BASE",          &[("OCTOSPI2_WINDOW", 0x1000_0000)]),
+        // RM0456: 256 Mbyte HSPI window
+        ("STM32U5.*:HSPI1_BASE",                        &[("HSPI1_WINDOW", 0x1000_0000)]),
+        // RM0477, RM0486: 256 Mbyte XSPI windows
+        ("STM32(H7[RS]|N6).*:XSPI1_BASE",               &[("XSPI1_WINDOW", 0x1000_0000)]),
+        ("STM32(H7[RS]|N6).*:XSPI2_BASE",               &[("XSPI2_WINDOW", 0x1000_0000)]),
+        ("STM32N6.*:XSPI3_BASE",                        &[("XSPI3_WINDOW", 0x1000_0000)]),
+    ];
+    static WINDOW_MAP: LazyLock<regex_map::RegexMap<&[(&str, u32)]>> =
+        LazyLock::new(|| new_regex_map(WINDOWS.iter().copied()));
+
+    for (define, peripheral) in DEFINES {
+        let Some(&regs) = peripherals.get(*peripheral) else {
+            continue;
+        };
+        let Some(base) = defines.0.get(*define).and_then(|&a| u32::try_from(a).ok()) else {
+            continue;
+        };
+        if base == regs {
+            continue;
+        }
+        let Some(windows) = WINDOW_MAP.get(&format!("{chip}:{define}")).next() else {
+            continue;
+        };
+
+        let mut address = base;
+        for (name, size) in windows.iter() {
+            for memories in memories.iter_mut() {
+                // Several defines can describe the same window, e.g. a bank and the SDRAM banks around it.
+                let end = u64::from(address) + u64::from(*size);
+                if memories.iter().any(|m| {
+                    u64::from(m.address) < end && u64::from(address) < u64::from(m.address) + u64::from(m.size)
+                }) {
+                    continue;
+                }
+                memories.push(Memory {
+                    name: name.to_string(),
+                    kind: memory::Kind::External,
+                    address,
+                    secure_address: None,
+                    size: *size,
+                    settings: None,
+                    access: None,
+                    attributes: Attributes::default(),
+                    peripheral: Some(peripheral.to_string()),
+                });
+                memories.sort_by_key(|m| (m.address, m.name.clone()));
+            }
+            address = address.wrapping_add(*size);
+        }
+    }
+}
+
+/// Add the system memory, option bytes and OTP regions found in the headers.
+///
+/// The headers only define the base address of these regions, and on some families the end
+/// address. `SIZES` below only covers F0, F1, F2, F3, F4, L0 and L1, so on the other families a
+/// region is left out unless the header defines its end. OTP regions already listed in the memory
+/// tables above are kept as they are.
+pub fn add_system_regions(chip: &str, memories: &mut [Vec<Memory>], defines: &crate::header::Defines) {
+    // (kind, memory name, base defines, end defines)
+    #[rustfmt::skip]
+    const REGIONS: &[(memory::Kind, &str, &[&str], &[&str])] = &[
+        (memory::Kind::SystemMemory, "SYSTEM_MEMORY", &["SYSTEM_MEMORY_BASE", "SYSTEM_FLASH_BASE"], &["SYSTEM_MEMORY_END", "SYSTEM_FLASH_END"]),
+        (memory::Kind::OptionBytes,  "OPTION_BYTES",  &["OB_BASE"],                                 &["OB_END"]),
+        (memory::Kind::Otp,          "OTP",           &["FLASH_OTP_BASE"],                          &["FLASH_OTP_END"]),
+    ];
+
+    // Sizes of the regions whose end is not in the headers, keyed by `chip:region`. The first match wins.
+    #[rustfmt::skip]
+    static SIZES: LazyLock<regex_map::RegexMap<u32>> = LazyLock::new(|| new_regex_map([
+        ("STM32F[013].*:OPTION_BYTES",          16),
+        ("STM32L[01].*:OPTION_BYTES",           32),
+        ("STM32F[24].*:OPTION_BYTES",           16),
+        // RM0008: the connectivity line has an 18 Kbyte bootloader, XL-density a 6 Kbyte one
+        ("STM32F10[57].*:SYSTEM_MEMORY",        18 * 1024),
+        ("STM32F10[13].[FG].*:SYSTEM_MEMORY",   6 * 1024),
+        ("STM32F1.*:SYSTEM_MEMORY",             2 * 1024),
+        ("STM32F[24].*:SYSTEM_MEMORY",          30 * 1024),
+        ("STM32F[24].*:OTP",                    528),
+    ]));
+
+    let get = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|n| defines.0.get(*n).filter(|&&a| a != 0))
+            .and_then(|&a| u32::try_from(a).ok())
+    };
+
+    for (kind, name, base, end) in REGIONS {
+        let Some(address) = get(base) else {
+            continue;
+        };
+        let Some(size) = get(end)
+            .filter(|&end| end > address)
+            .map(|end| end - address + 1)
+            .or_else(|| SIZES.get(&format!("{chip}:{name}")).next().copied())
+        else {
+            continue;
+        };
+
+        for memories in memories.iter_mut() {
+            if memories.iter().any(|m| m.kind == *kind) {
+                continue;
+            }
+            memories.push(Memory {
+                name: name.to_string(),
+                kind: kind.clone(),
+                address,
+                secure_address: None,
+                size,
+                settings: None,
+                access: None,
+                attributes: Attributes::default(),
+                peripheral: None,
+            });
+            memories.sort_by_key(|m| (m.address, m.name.clone()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flash_layout_f4() {
+        let flash = ChipMemories::new().flash("STM32F427ZG").unwrap();
+        assert_eq!(flash.len(), 2);
+
+        // Single bank: 4 x 16K, 64K, then 128K sectors
+        let single = &flash[0];
+        assert_eq!(single.banks.len(), 1);
+        let expected: Vec<(u32, u32)> = (0..12)
+            .map(|i| match i {
+                0..4 => (i, 16 * 1024),
+                4 => (i, 64 * 1024),
+                _ => (i, 128 * 1024),
+            })
+            .collect();
+        let sectors: Vec<(u32, u32)> = single.banks[0].sectors.iter().map(|s| (s.index, s.size)).collect();
+        assert_eq!(sectors, expected);
+        assert_eq!(
+            single.bank_mode.as_ref().map(|m| (m.field.as_str(), m.value)),
+            Some(("DB1M", 0))
+        );
+        assert_eq!(single.read_while_write, Some(false));
+        assert_eq!(single.bank_swap, None);
+
+        // Dual bank: the second bank starts at sector 12, BFB2 swaps the banks
+        let dual = &flash[1];
+        assert_eq!(dual.banks.len(), 2);
+        assert_eq!(
+            (dual.banks[0].name.as_str(), dual.banks[0].size),
+            ("BANK_1", 512 * 1024)
+        );
+        assert_eq!(
+            (dual.banks[1].name.as_str(), dual.banks[1].address),
+            ("BANK_2", 0x0808_0000)
+        );
+        let index = |bank: &flash::Bank| bank.sectors.iter().map(|s| s.index).collect::<Vec<_>>();
+        assert_eq!(index(&dual.banks[0]), (0..8).collect::<Vec<_>>());
+        assert_eq!(index(&dual.banks[1]), (12..20).collect::<Vec<_>>());
+        assert_eq!(
+            dual.bank_mode.as_ref().map(|m| (m.field.as_str(), m.value)),
+            Some(("DB1M", 1))
+        );
+        assert_eq!(
+            dual.bank_swap.as_ref().map(|b| (b.register.as_str(), b.field.as_str())),
+            Some(("OPTCR", "BFB2"))
+        );
+        assert_eq!(dual.read_while_write, Some(true));
+    }
+
+    #[test]
+    fn test_flash_page_numbering() {
+        let memories = ChipMemories::new();
+
+        // G0: the second bank starts at page 256
+        let flash = memories.flash("STM32G0B1RC").unwrap();
+        let dual = flash.iter().find(|f| f.banks.len() == 2).unwrap();
+        assert_eq!(dual.banks[0].sectors.len(), 64);
+        assert_eq!(dual.banks[1].sectors.first().map(|s| s.index), Some(256));
+        assert_eq!(dual.banks[1].sectors.last().map(|s| s.index), Some(319));
+
+        // F1 XL-density: 2K pages, numbered across both banks
+        let flash = memories.flash("STM32F103RG").unwrap();
+        assert_eq!(flash.len(), 1);
+        let banks = &flash[0].banks;
+        assert_eq!(banks.len(), 2);
+        assert_eq!((banks[1].address, banks[1].size), (0x0808_0000, 512 * 1024));
+        assert!(banks.iter().flat_map(|b| &b.sectors).all(|s| s.size == 2048));
+        assert_eq!(banks[1].sectors.first().map(|s| (s.index, s.offset)), Some((256, 0)));
     }
 }
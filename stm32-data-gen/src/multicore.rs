@@ -0,0 +1,126 @@
+use std::collections::{BTreeSet, HashSet};
+use std::sync::LazyLock;
+
+use stm32_data_serde::chip::multicore::{CoreView, Mailbox, MemoryView};
+use stm32_data_serde::chip::{Core, Memory, MultiCore};
+
+use crate::util::new_regex_map;
+
+#[derive(Debug, Clone, Copy)]
+enum View {
+    /// The core cannot reach the region.
+    Hidden,
+    /// The core sees the region at another address.
+    At(u32),
+}
+
+/// How a core sees a memory region, keyed by `chip:core:region`. Regions without a match are
+/// seen at their own address. The first match wins.
+#[rustfmt::skip]
+static CORE_MEMORY_VIEWS: LazyLock<regex_map::RegexMap<View>> = LazyLock::new(|| new_regex_map([
+    // H7 dual-core: the TCMs are private to the CM7, the CM4 runs from the D2 SRAMs through
+    // their alias at 0x10000000.
+    ("STM32H7(45|47|55|57).*:cm4:(ITCM|DTCM)", View::Hidden),
+    ("STM32H7(45|47|55|57).*:cm4:SRAM1",       View::At(0x10000000)),
+    ("STM32H7(45|47|55|57).*:cm4:SRAM2",       View::At(0x10020000)),
+    ("STM32H7(45|47|55|57).*:cm4:SRAM3",       View::At(0x10040000)),
+]));
+
+/// Number of semaphores or channels of the inter-core mailboxes, keyed by `chip:peripheral`.
+#[rustfmt::skip]
+static MAILBOXES: LazyLock<regex_map::RegexMap<u8>> = LazyLock::new(|| new_regex_map([
+    ("STM32H7(45|47|55|57).*:HSEM", 32),
+    ("STM32WB.*:HSEM",              32),
+    ("STM32WB.*:IPCC",               6),
+    ("STM32WL5.*:HSEM",             16),
+    ("STM32WL5.*:IPCC",              6),
+]));
+
+/// Describe how the cores of `chip_name` share peripherals, interrupts and memory.
+/// Returns `None` for single-core chips.
+pub fn multicore(chip_name: &str, memory: &[Vec<Memory>], cores: &[Core]) -> Option<MultiCore> {
+    if cores.len() < 2 {
+        return None;
+    }
+
+    let peripherals: Vec<BTreeSet<&str>> = cores
+        .iter()
+        .map(|c| c.peripherals.iter().map(|p| p.name.as_str()).collect())
+        .collect();
+    let interrupts: Vec<BTreeSet<&str>> = cores
+        .iter()
+        .map(|c| c.interrupts.iter().map(|i| i.name.as_str()).collect())
+        .collect();
+    let shared_peripherals: BTreeSet<&str> = peripherals
+        .iter()
+        .skip(1)
+        .fold(peripherals[0].clone(), |acc, p| acc.intersection(p).copied().collect());
+
+    // The same region appears once per memory variant, keep its first occurrence.
+    let mut seen = HashSet::new();
+    let regions: Vec<&Memory> = memory.iter().flatten().filter(|m| seen.insert(&m.name)).collect();
+
+    let views = cores
+        .iter()
+        .enumerate()
+        .map(|(i, core)| {
+            let others = |sets: &[BTreeSet<&str>]| -> BTreeSet<String> {
+                sets.iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .flat_map(|(_, s)| s.iter().map(|n| n.to_string()))
+                    .collect()
+            };
+            let other_peripherals = others(&peripherals);
+            let other_interrupts = others(&interrupts);
+
+            let memory = regions
+                .iter()
+                .filter_map(|m| {
+                    let key = format!("{}:{}:{}", chip_name, core.name, m.name);
+                    let address = match CORE_MEMORY_VIEWS.get(&key).next() {
+                        Some(View::Hidden) => return None,
+                        Some(View::At(address)) => *address,
+                        None => m.address,
+                    };
+                    Some(MemoryView {
+                        name: m.name.clone(),
+                        address,
+                    })
+                })
+                .collect();
+
+            CoreView {
+                core: core.name.clone(),
+                private_peripherals: peripherals[i]
+                    .iter()
+                    .filter(|p| !other_peripherals.contains(**p))
+                    .map(|p| p.to_string())
+                    .collect(),
+                private_interrupts: interrupts[i]
+                    .iter()
+                    .filter(|p| !other_interrupts.contains(**p))
+                    .map(|p| p.to_string())
+                    .collect(),
+                memory,
+            }
+        })
+        .collect();
+
+    let mailboxes = shared_peripherals
+        .iter()
+        .filter_map(|p| {
+            let channels = *MAILBOXES.get(&format!("{chip_name}:{p}")).next()?;
+            Some(Mailbox {
+                peripheral: p.to_string(),
+                channels,
+            })
+        })
+        .collect();
+
+    Some(MultiCore {
+        shared_peripherals: shared_peripherals.iter().map(|p| p.to_string()).collect(),
+        cores: views,
+        mailboxes,
+    })
+}
@@ -2,7 +2,7 @@ use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use anyhow::Ok;
+use anyhow::{Context, Ok};
 use itertools::Itertools;
 use log::trace;
 use serde::{Deserialize, Serialize};
@@ -853,23 +853,84 @@ fn build_peripherals(f: &peripherals::File) -> BuildPeripherals {
         .collect()
 }
 
-fn build_dma(f: &dma::File, dma_map: &DmaMap, chip_name: &str) -> ChipDma {
+/// Request or trigger ids of the `kind` interconnects of a DMA instance, along with the interconnect.
+///
+/// The ids come from the table in `data/gpdma.yaml` if there is one. Otherwise the position in the
+/// descriptor is used, but only if the descriptor lists every id up to `max_id`.
+fn interconnect_ids<'a>(
+    f: &'a dma::File,
+    instance: &dma::Instance,
+    kind: &str,
+    max_id: i64,
+    table: Option<&HashMap<String, u8>>,
+) -> anyhow::Result<Vec<(&'a dma::Interconnect, Option<u8>)>> {
+    let interconnects: Vec<_> = f
+        .interconnect
+        .iter()
+        .filter(|c| c.dma_instance == instance.name)
+        .filter(|c| c.type_field == kind)
+        .collect();
+    let dense = max_id > 0 && interconnects.len() as i64 == max_id + 1;
+
+    interconnects
+        .into_iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let id = match table {
+                Some(table) => table.get(&c.event.to_ascii_uppercase()).copied(),
+                None if dense => u8::try_from(i).ok(),
+                None => None,
+            };
+            if let Some(id) = id
+                && max_id > 0
+                && i64::from(id) > max_id
+            {
+                anyhow::bail!(
+                    "{}: {} id {} of {} is above the maximum {}",
+                    instance.name,
+                    kind,
+                    id,
+                    c.event,
+                    max_id
+                );
+            }
+            Ok((c, id))
+        })
+        .collect()
+}
+
+fn build_dma(f: &dma::File, dma_map: &DmaMap, chip_name: &str) -> anyhow::Result<ChipDma> {
     let mut peripherals: HashMap<String, Vec<peripheral::DmaChannel>> = HashMap::with_capacity(f.interconnect.len());
+    let mut triggers: HashMap<String, Vec<peripheral::DmaTrigger>> = HashMap::new();
 
     for instance in &f.instances {
-        let request_map = dma_map.get(&format!("{}:{}:{}", chip_name, instance.name, instance.digital_name));
-
-        for interconnect in f
-            .interconnect
+        let tables = dma_map.get(&format!("{}:{}:{}", chip_name, instance.name, instance.digital_name));
+        let request_select = ["GPDMA", "HPDMA", "LPDMA"]
             .iter()
-            .filter(|c| c.dma_instance == instance.name)
-            .filter(|c| c.type_field == "request")
-        {
+            .any(|kind| instance.name.starts_with(kind));
+
+        let requests = interconnect_ids(
+            f,
+            instance,
+            "request",
+            instance.features.g_max_req_id,
+            tables.and_then(|t| t.requests.as_ref()),
+        )?;
+        for (interconnect, request) in requests {
             let Some(signal_id) = &interconnect.signal_id else {
                 trace!("failed to get signal_id for {}", interconnect.event);
                 continue;
             };
 
+            if request_select && request.is_none() {
+                anyhow::bail!(
+                    "{}: {} request {} has no request number, add it to data/gpdma.yaml",
+                    chip_name,
+                    instance.name,
+                    interconnect.event
+                );
+            }
+
             peripherals
                 .entry(interconnect.instance.clone())
                 .or_default()
@@ -879,18 +940,42 @@ fn build_dma(f: &dma::File, dma_map: &DmaMap, chip_name: &str) -> ChipDma {
                     channel: None,
                     dmamux: None,
                     remap: Vec::new(),
-                    request: if let Some(request_map) = request_map
-                        && let Some(request) = request_map.get(&interconnect.event.to_ascii_uppercase())
-                    {
-                        Some(*request)
-                    } else {
-                        None
-                    },
+                    request,
+                });
+        }
+
+        let trigger_table = tables.and_then(|t| t.triggers.as_ref());
+        let trigger_ids = interconnect_ids(f, instance, "trigger", instance.features.g_max_trig_id, trigger_table)?;
+        for (interconnect, trigger) in trigger_ids {
+            let Some(signal_id) = &interconnect.signal_id else {
+                trace!("failed to get signal_id for {}", interconnect.event);
+                continue;
+            };
+            let Some(trigger) = trigger else {
+                if trigger_table.is_none() {
+                    trace!("failed to get trigger id for {}", interconnect.event);
+                    continue;
+                }
+                anyhow::bail!(
+                    "{}: {} trigger {} has no trigger number, add it to data/gpdma.yaml",
+                    chip_name,
+                    instance.name,
+                    interconnect.event
+                );
+            };
+
+            triggers
+                .entry(interconnect.instance.clone())
+                .or_default()
+                .push(peripheral::DmaTrigger {
+                    dma: instance.name.clone(),
+                    signal: signal_id.to_uppercase(),
+                    trigger,
                 });
         }
     }
 
-    ChipDma {
+    Ok(ChipDma {
         peripherals: peripherals,
         channels: f
             .instances
@@ -907,7 +992,9 @@ fn build_dma(f: &dma::File, dma_map: &DmaMap, chip_name: &str) -> ChipDma {
             })
             .flatten()
             .collect(),
-    }
+        dmamux_inputs: HashMap::new(),
+        triggers,
+    })
 }
 
 fn build_exti(f: &exti::File) -> HashMap<String, String> {
@@ -984,7 +1071,7 @@ impl PackageDirectory {
         })?;
 
         let dma = self.dma.get_or_try_insert_with(dma.to_path_buf(), || {
-            Ok(build_dma(&serde_json::from_str(&load_file(dma)?)?, dma_map, chip_name))
+            build_dma(&serde_json::from_str(&load_file(dma)?)?, dma_map, chip_name)
         })?;
 
         let exti = self.exti.get_or_try_insert_with(exti.to_path_buf(), || {
@@ -999,21 +1086,55 @@ impl PackageDirectory {
     }
 }
 
+/// Request and trigger tables overriding the ones derived from a DMA descriptor.
+struct DmaTables {
+    requests: Option<HashMap<String, u8>>,
+    triggers: Option<HashMap<String, u8>>,
+}
+
 struct DmaMap {
-    map: regex_map::RegexMap<HashMap<String, u8>>,
+    map: regex_map::RegexMap<DmaTables>,
 }
 
 impl DmaMap {
-    pub fn new(map: &[(&str, &str)]) -> anyhow::Result<Self> {
+    pub fn parse() -> anyhow::Result<Self> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Rule {
+            #[serde(rename = "match")]
+            pattern: String,
+            requests: Option<String>,
+            triggers: Option<String>,
+        }
+
+        const PATH: &str = "data/gpdma.yaml";
+        let rules: Vec<Rule> = serde_yaml::from_str(&std::fs::read_to_string(PATH).context(PATH)?).context(PATH)?;
+
+        let mut map = Vec::with_capacity(rules.len());
+        for rule in rules {
+            regex::Regex::new(&rule.pattern).with_context(|| format!("{}: invalid match {}", PATH, rule.pattern))?;
+            if rule.requests.is_none() && rule.triggers.is_none() {
+                anyhow::bail!("{}: {} has neither requests nor triggers", PATH, rule.pattern);
+            }
+            let tables = DmaTables {
+                requests: rule
+                    .requests
+                    .map(|file| load_dma_mux(&file).context(format!("{}: {}", PATH, file)))
+                    .transpose()?,
+                triggers: rule
+                    .triggers
+                    .map(|file| load_dma_mux(&format!("triggers/{file}")).context(format!("{}: {}", PATH, file)))
+                    .transpose()?,
+            };
+            map.push((rule.pattern, tables));
+        }
+
         Ok(Self {
-            map: new_regex_map(
-                map.iter()
-                    .map(|(k, v)| (k, load_dma_mux(v).expect(&format!("failed to load: {v}")))),
-            ),
+            map: new_regex_map(map),
         })
     }
 
-    pub fn get(&self, key: &str) -> Option<&HashMap<String, u8>> {
+    pub fn get(&self, key: &str) -> Option<&DmaTables> {
         self.map.get(key).next()
     }
 }
@@ -1029,9 +1150,9 @@ pub fn parse_packages(
     let mut files: Vec<_> = glob::glob("sources/cubeprogdb2/**/*.pdsc")?.collect::<Result<_, _>>()?;
     files.sort();
 
-    // This can be parallelized by parallelizing the dma map constructor and parallelizing the parsing of each package
+    // This can be parallelized by parallelizing the parsing of each package
 
-    let dma_map = DmaMap::new(&[("STM32C5.*:LPDMA.*:.*", "C5_LPDMA.yaml")])?;
+    let dma_map = DmaMap::parse()?;
 
     for f in files {
         if let Some(filter) = filter
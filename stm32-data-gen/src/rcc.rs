@@ -1,10 +1,13 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::LazyLock;
 
 use anyhow::{Ok, anyhow, bail};
-use chiptool::ir::IR;
+use chiptool::ir::{Array, BlockItemInner, IR};
+use itertools::Itertools;
 use lazy_regex::regex;
 use stm32_data_serde::chip::core::peripheral::rcc::{Field, StopMode};
 use stm32_data_serde::chip::core::peripheral::{self, rcc};
+use stm32_data_serde::{ClockTree, clock_tree};
 
 use crate::registers::Registers;
 
@@ -20,22 +23,97 @@ struct ParsedRcc {
     en_rst: HashMap<String, EnRst>,
     /// name -> mux info
     mux: HashMap<String, MuxInfo>,
+    clock_tree: ClockTree,
 }
 
 #[derive(Debug)]
 struct MuxInfo {
     mux: Field,
     variants: Vec<String>,
+    sources: Vec<rcc::MuxSource>,
 }
 
 #[derive(Debug, Clone)]
 struct EnRst {
     enable: rcc::Field,
     reset: Option<rcc::Field>,
+    low_power_enable: Option<rcc::Field>,
+    autonomous_enable: Option<rcc::Field>,
     bus_clock: String,
     stop_mode: StopMode,
 }
 
+/// Prescalers by `<rcc version>:<field>`, with the clock they divide and the clock they produce. `$n`
+/// is a capture of the pattern, `{n}` the register array element counting from 1, `{pll}` the PLL
+/// the field belongs to, `{plls}` the same or `PLL` when the field is shared by all PLLs, and
+/// `{pll_in}` the output of the mux selecting the PLL input. The input lists alternatives separated by `|`; the first one the tree
+/// has is used, the last one otherwise. The first matching pattern wins.
+#[rustfmt::skip]
+static PRESCALERS: LazyLock<Vec<(regex::Regex, &str, &str)>> = LazyLock::new(|| {
+    [
+        // System, CPU and bus clocks.
+        (r"n6:HPRE",                      "SYS",                "HCLK"),
+        (r".*:HPRE",                      "CPU|SYS_DIV|SYS",    "HCLK"),
+        (r".*:(?:D1C|C)PRE",              "SYS",                "CPU"),
+        (r".*:BMPRE",                     "CPU",                "HCLK"),
+        (r".*:SYSDIV",                    "SYS",                "SYS_DIV"),
+        (r"wb0:CLKSYSDIV_REQ",            "HSIPLL",             "SYS"),
+        (r".*:C2HPRE",                    "SYS",                "CPU2"),
+        (r".*:SHDHPRE",                   "SYS",                "HCLK3"),
+        (r".*:(?:HPRE|HDIV)5",            "SYS",                "HCLK5"),
+        (r".*:PPRE",                      "HCLK",               "PCLK1"),
+        (r".*:D2PPRE(\d)",                "HCLK",               "PCLK${1}"),
+        (r".*:D1PPRE",                    "HCLK",               "PCLK3"),
+        (r".*:D3PPRE",                    "HCLK",               "PCLK4"),
+        (r".*:PPRE(\d)",                  "HCLK",               "PCLK${1}"),
+        (r"n6:TIMPRE",                    "SYS",                "TIMG"),
+        (r"u5:DPRE",                      "HCLK",               "DCLK"),
+        // PLL input dividers.
+        (r".*:(?:PLL|DIV|PLLDIV)M",       "{pll_in}",           "{plls}_REF"),
+        (r".*:PREDIV",                    "PLL_IN",             "PLL_REF"),
+        (r".*:PREDIV1",                   "PREDIV1|HSE",        "PREDIV1_DIV"),
+        (r".*:PREDIV2",                   "HSE",                "PREDIV2_DIV"),
+        (r".*:PLLXTPRE",                  "HSE",                "HSE_DIV"),
+        (r"u5:PLLMBOOST",                 "{pll_in}",           "BOOST"),
+        (r"u3:BOOSTDIV",                  "BOOST",              "BOOST_DIV"),
+        // PLL output dividers.
+        (r".*:PLL([PQRST])",              "{pll}_VCO",          "{pll}_${1}"),
+        (r".*:PLLPBIT",                   "{pll}_VCO",          "{pll}_P"),
+        (r"l[01].*:PLLDIV",               "{pll}_VCO",          "{pll}_R"),
+        (r"n6:PLLPDIV1",                  "{pll}_VCO",          "{pll}_PDIV"),
+        (r"n6:PLLPDIV2",                  "{pll}_PDIV",         "{pll}_P"),
+        (r".*:PLL(?:SAI|I2S)?DIV([QR])",  "{pll}_${1}",           "{pll}_${1}_DIV"),
+        (r".*:PLLMCODIV",                 "PLL1_P",             "PLL1_P_MCO"),
+        (r"n6:ICINT",                     "IC{n}_IN",           "IC{n}"),
+        // Oscillator dividers.
+        (r".*:HSIDIV",                    "HSI",                "HSI_DIV"),
+        (r".*:HSIKERDIV",                 "HSI48|HSI",          "HSIKER"),
+        (r".*:HSEPRE",                    "HSE",                "HSE_DIV"),
+        (r"n6:HSEDIV2BYP",                "HSE",                "HSE_DIV"),
+        (r"n6:HSECSSBPRE",                "HSE",                "HSE_CSS"),
+        (r".*:LSI1?PREDIV",               "LSI1|LSI",           "LSI_DIV"),
+        (r"u3:MSI([SK])DIV",              "MSI${1}",              "MSI${1}_DIV"),
+        (r"u3:MSIHSINDIV",                "HSE",                "MSI_HSE"),
+        (r".*:RTCPRE",                    "HSE",                "HSE_RTC"),
+        // Clock outputs.
+        (r".*:MCO(\d?)PRE",               "MCO${1}",              "MCO${1}_DIV"),
+        (r"wb0:CCOPRE",                   "MCO",                "MCO_DIV"),
+        // Kernel clocks.
+        (r"n6:ADCPRE",                    "ADC12",              "ADC12_DIV"),
+        (r".*:ADCPRE",                    "PCLK2",              "ADC"),
+        (r".*:ADC(1|12|34)PRES",          "PLL1_P",             "ADC${1}"),
+        (r".*:ADCDACPRE",                 "ADCDAC",             "ADCDAC_DIV"),
+        (r"f37:SDPRE",                    "SYS",                "SDADC"),
+        (r"g0.*:RNGDIV",                  "RNG",                "RNG_DIV"),
+        (r".*:ETH1PTPDIV",                "ETH1PTP|ETH1PTPCLK", "ETH1PTP_DIV"),
+        (r".*:ETH1CLKDIV",                "ETH1CLK",            "ETH1CLK_DIV"),
+        (r"wb0:SMPSDIV",                  "HSIPLL",             "SMPS"),
+    ]
+    .into_iter()
+    .map(|(k, input, output)| (regex::Regex::new(&format!("^{k}$")).unwrap(), input, output))
+    .collect()
+});
+
 impl ParsedRccs {
     /// Parse the RCC information from the `rcc_xx` yaml files in `data/registers`
     pub fn parse(registers: &Registers) -> anyhow::Result<Self> {
@@ -50,6 +128,18 @@ impl ParsedRccs {
         Ok(Self { rccs })
     }
 
+    /// Write the clock tree of every RCC version to `build/data/rcc`
+    pub fn write(&self) -> anyhow::Result<()> {
+        std::fs::create_dir_all("build/data/rcc")?;
+
+        for (name, rcc) in &self.rccs {
+            let dump = serde_json::to_string_pretty(&rcc.clock_tree)?;
+            std::fs::write(format!("build/data/rcc/{name}.json"), dump)?;
+        }
+
+        Ok(())
+    }
+
     /// Parse mcu specific RCC information from the IR object
     /// - Clock source muxes of peripherals
     fn parse_rcc(rcc_version: &str, ir: &IR) -> anyhow::Result<ParsedRcc> {
@@ -163,6 +253,11 @@ impl ParsedRccs {
             "IC18",
             "IC19",
             "IC20",
+            // N6 ICx dividers select a whole PLL
+            "PLL1",
+            "PLL2",
+            "PLL3",
+            "PLL4",
             "HSI_DIV",
             "HSE_RTC",
             "HSE_DIV2_OSC", // TODO: remove this
@@ -235,6 +330,15 @@ impl ParsedRccs {
                             field: field.name.clone(),
                         },
                         variants: enumm.variants.iter().map(|v| v.name.clone()).collect(),
+                        sources: enumm
+                            .variants
+                            .iter()
+                            .sorted_by_key(|v| v.value)
+                            .map(|v| rcc::MuxSource {
+                                name: v.name.clone(),
+                                value: v.value as u32,
+                            })
+                            .collect(),
                     };
 
                     if mux.insert(peri.to_string(), val).is_some() {
@@ -244,6 +348,21 @@ impl ParsedRccs {
             }
         }
 
+        let find_field = |reg: &str, field: &str| {
+            let body = ir.fieldsets.get(reg)?;
+            body.fields.iter().find(|f| f.name == field).map(|_| rcc::Field {
+                register: reg.to_string(),
+                field: field.to_string(),
+            })
+        };
+        // U5/U3 put most autonomous mode bits in SRDAMR instead of next to the enable bits.
+        let amr_regs: Vec<&String> = ir
+            .fieldsets
+            .keys()
+            .filter(|reg| regex!(r"^(.+AMENR\d?|SRDAMR)$").is_match(reg))
+            .sorted()
+            .collect();
+
         // Parse xxEN/xxRST bits.
         let mut en_rst = HashMap::new();
         for (reg, body) in &ir.fieldsets {
@@ -256,8 +375,8 @@ impl ParsedRccs {
                 };
 
                 for field in &body.fields {
-                    if let Some(peri) = field.name.strip_suffix("EN") {
-                        let peri = if peri == "RTCAPB" { "RTC" } else { peri };
+                    if let Some(prefix) = field.name.strip_suffix("EN") {
+                        let peri = if prefix == "RTCAPB" { "RTC" } else { prefix };
 
                         let mut reset = None;
                         if let Some(rstr) = ir.fieldsets.get(&reg.replace("ENR", "RSTR")) {
@@ -269,6 +388,15 @@ impl ParsedRccs {
                             }
                         }
 
+                        // Sleep mode enable bits are xxLPEN in xxLPENR (F2/F4/F7/L1/H7) or
+                        // xxSMEN in xxSMENR (L4/L5/U5/G4/WB/WBA).
+                        let low_power_enable = find_field(&reg.replace("ENR", "LPENR"), &format!("{prefix}LPEN"))
+                            .or_else(|| find_field(&reg.replace("ENR", "SMENR"), &format!("{prefix}SMEN")));
+
+                        let autonomous_field = format!("{prefix}AMEN");
+                        let autonomous_enable = find_field(&reg.replace("ENR", "AMENR"), &autonomous_field)
+                            .or_else(|| amr_regs.iter().find_map(|amr| find_field(amr, &autonomous_field)));
+
                         let clock = clock.replace("AHB", "HCLK").replace("APB", "PCLK");
 
                         let val = EnRst {
@@ -277,6 +405,8 @@ impl ParsedRccs {
                                 field: field.name.clone(),
                             },
                             reset,
+                            low_power_enable,
+                            autonomous_enable,
                             bus_clock: clock,
                             // The stop mode info is set in `low_power.rs`
                             stop_mode: StopMode::default(),
@@ -290,7 +420,311 @@ impl ParsedRccs {
             }
         }
 
-        Ok(ParsedRcc { en_rst, mux })
+        let clock_tree = Self::parse_clock_tree(rcc_version, ir, &en_rst, &allowed_variants)?;
+
+        Ok(ParsedRcc {
+            en_rst,
+            mux,
+            clock_tree,
+        })
+    }
+
+    /// Build the clock graph of an RCC version
+    /// - Clock sources from the oscillator enable bits
+    /// - PLLs from the PLL enable bits, with their outputs taken from the mux inputs and post-dividers
+    /// - Prescalers from the fields whose enum only has `DIVn` variants, wired up by `PRESCALERS`
+    /// - Every mux whose inputs are clocks, including the system clock switch and the PLL source
+    /// - Bus clocks from the en/rst registers and the mux inputs, with the prescaler output driving them
+    fn parse_clock_tree(
+        rcc_version: &str,
+        ir: &IR,
+        en_rst: &HashMap<String, EnRst>,
+        allowed_variants: &HashSet<&str>,
+    ) -> anyhow::Result<ClockTree> {
+        let mut sources = BTreeSet::new();
+        let mut pll_names = BTreeSet::new();
+        // (register, field, index, divisors), wired up once all clocks are known.
+        let mut dividers = Vec::new();
+        let mut selects = Vec::new();
+
+        let Some(block) = ir.blocks.get("RCC") else {
+            bail!("rcc: missing block RCC for rcc_{}", rcc_version);
+        };
+        for item in &block.items {
+            let BlockItemInner::Register(reg) = &item.inner else {
+                continue;
+            };
+            let Some(fieldset) = reg.fieldset.as_deref().and_then(|f| ir.fieldsets.get(f)) else {
+                continue;
+            };
+
+            for field in &fieldset.fields {
+                let array_len = array_len(item.array.as_ref()).max(array_len(field.array.as_ref()));
+                let indices: Vec<Option<u32>> = match array_len {
+                    0 => vec![None],
+                    len => (0..len).map(Some).collect(),
+                };
+
+                if let Some(m) =
+                    regex!(r"^(HSI|HSE|LSI\d?|LSE|CSI|MSI|MSIS|MSIK|HSI48|SHSI|PSI|HSIPLL)ON$").captures(&field.name)
+                {
+                    sources.insert(m.get(1).unwrap().as_str().to_string());
+                } else if let Some(m) = regex!(r"^(PLL(SAI|I2S)?\d?)ON$").captures(&field.name) {
+                    let name = m.get(1).unwrap().as_str();
+                    // Single PLLs are named PLL1 in the mux variants, PLLI2S is PLLI2S1.
+                    if array_len > 0 {
+                        pll_names.extend((1..=array_len).map(|i| format!("{name}{i}")));
+                    } else if name.ends_with(|c: char| c.is_ascii_digit()) {
+                        pll_names.insert(name.to_string());
+                    } else {
+                        pll_names.insert(format!("{name}1"));
+                    }
+                }
+
+                let Some(enumm) = field.enumm.as_deref().and_then(|e| ir.enums.get(e)) else {
+                    continue;
+                };
+
+                let divisors: Option<Vec<u32>> = enumm
+                    .variants
+                    .iter()
+                    .map(|v| {
+                        regex!(r"^(?i:DIV)_?(\d+)$")
+                            .captures(&v.name)
+                            .map(|m| m.get(1).unwrap().as_str().parse().unwrap())
+                    })
+                    .collect();
+                if let Some(mut divisors) = divisors.filter(|d| !d.is_empty()) {
+                    divisors.sort();
+                    divisors.dedup();
+                    for &index in &indices {
+                        dividers.push((item.name.clone(), field.name.clone(), index, divisors.clone()));
+                    }
+                    continue;
+                }
+
+                if !regex!(r"^.+(SEL|SRC)$|^(.+)?SW$").is_match(&field.name) {
+                    continue;
+                }
+                let inputs: Vec<String> = enumm.variants.iter().map(|v| v.name.clone()).collect();
+                let is_clock = |v: &str| allowed_variants.contains(strip_fixed_divider(v));
+                if !inputs.iter().any(|v| is_clock(v)) {
+                    continue;
+                }
+                for &index in &indices {
+                    selects.push((item.name.clone(), field.name.clone(), index, inputs.clone()));
+                }
+            }
+        }
+
+        let mut muxes: Vec<clock_tree::Mux> = selects
+            .into_iter()
+            .map(|(register, field, index, inputs)| {
+                let output = if regex!(r"^PLL(SAI|I2S)?\d?(SRC|SEL)$").is_match(&field) {
+                    match pll_of(&register, &field, index, &pll_names) {
+                        Some((pll, true)) => format!("{pll}_IN"),
+                        _ => "PLL_IN".to_string(),
+                    }
+                } else {
+                    let stem = match field.as_str() {
+                        "SW" | "SYSSW" => "SYS".to_string(),
+                        "CPUSW" => "CPU".to_string(),
+                        _ => regex!(r"^(.+?)_?(SEL|SRC|SW)$").replace(&field, "$1").to_string(),
+                    };
+                    // An element of an array of dividers, e.g. `ICCFGR[1].ICSEL` feeds the `IC2` divider.
+                    match index {
+                        Some(index) => format!("{stem}{}_IN", index + 1),
+                        None => stem,
+                    }
+                };
+                clock_tree::Mux {
+                    register,
+                    field,
+                    index,
+                    output,
+                    inputs,
+                }
+            })
+            .collect();
+
+        // The prescaler outputs don't depend on their inputs, so collect them first to resolve the
+        // input alternatives against every clock of the tree.
+        let mut wired = Vec::new();
+        for (register, field, index, divisors) in dividers {
+            let key = format!("{rcc_version}:{field}");
+            let Some((re, input, output)) = PRESCALERS.iter().find(|(re, _, _)| re.is_match(&key)) else {
+                bail!(
+                    "rcc: unknown prescaler {}.{} for rcc_{}, add it to PRESCALERS",
+                    register,
+                    field,
+                    rcc_version
+                );
+            };
+            let captures = re.captures(&key).unwrap();
+            let pll = pll_of(&register, &field, index, &pll_names);
+            let expand = |template: &str| -> anyhow::Result<String> {
+                let mut s = String::new();
+                captures.expand(template, &mut s);
+                if s.contains("{n}") {
+                    let Some(index) = index else {
+                        bail!(
+                            "rcc: prescaler {}.{} for rcc_{} is not an array",
+                            register,
+                            field,
+                            rcc_version
+                        );
+                    };
+                    s = s.replace("{n}", &(index + 1).to_string());
+                }
+                if s.contains("{pll") {
+                    let Some((pll, specific)) = &pll else {
+                        bail!(
+                            "rcc: no PLL for prescaler {}.{} for rcc_{}",
+                            register,
+                            field,
+                            rcc_version
+                        );
+                    };
+                    s = s.replace("{plls}", if *specific { pll } else { "PLL" });
+                    let pll_in = [format!("{pll}_IN"), "PLL_IN".to_string()]
+                        .into_iter()
+                        .find(|c| muxes.iter().any(|m| &m.output == c))
+                        .unwrap_or_else(|| "PLL_IN".to_string());
+                    s = s.replace("{pll_in}", &pll_in).replace("{pll}", pll);
+                }
+                Ok(s)
+            };
+            let input = expand(input)?;
+            let output = expand(output)?;
+            wired.push((register, field, index, input, output, divisors));
+        }
+
+        let mut clocks: HashSet<String> = sources.iter().cloned().collect();
+        clocks.extend(muxes.iter().map(|m| m.output.clone()));
+        clocks.extend(wired.iter().map(|(_, _, _, _, output, _)| output.clone()));
+        clocks.extend(pll_names.iter().map(|pll| format!("{pll}_VCO")));
+        let resolve = |alternatives: &str| -> String {
+            let last = alternatives.rsplit('|').next().unwrap();
+            alternatives
+                .split('|')
+                .find(|c| clocks.contains(*c))
+                .unwrap_or(last)
+                .to_string()
+        };
+
+        let mut prescalers: Vec<clock_tree::Prescaler> = wired
+            .into_iter()
+            .map(
+                |(register, field, index, input, output, divisors)| clock_tree::Prescaler {
+                    register,
+                    field,
+                    index,
+                    input: resolve(&input),
+                    output,
+                    divisors,
+                },
+            )
+            .collect();
+
+        let plls = pll_names
+            .iter()
+            .map(|name| {
+                let source = [format!("{name}_IN"), "PLL_IN".to_string()]
+                    .iter()
+                    .find_map(|output| muxes.iter().find(|m| &m.output == output))
+                    .map(|m| rcc::Field {
+                        register: m.register.clone(),
+                        field: m.field.clone(),
+                    });
+                let reference = [format!("{name}_REF"), "PLL_REF".to_string()]
+                    .into_iter()
+                    .find(|c| clocks.contains(c));
+                let input = if reference.is_some() {
+                    reference
+                } else {
+                    source.as_ref().and_then(|source| {
+                        muxes
+                            .iter()
+                            .find(|m| m.register == source.register && m.field == source.field)
+                            .map(|m| m.output.clone())
+                    })
+                };
+
+                let prefix = format!("{name}_");
+                let outputs: BTreeSet<String> = muxes
+                    .iter()
+                    .flat_map(|m| &m.inputs)
+                    .map(|v| strip_fixed_divider(v).to_string())
+                    .chain(prescalers.iter().flat_map(|p| [p.input.clone(), p.output.clone()]))
+                    .filter(|v| {
+                        v.strip_prefix(&prefix).is_some_and(|output| {
+                            regex!(r"^[A-Z]$").is_match(output)
+                                || (output == "VCO" && muxes.iter().flat_map(|m| &m.inputs).any(|i| i == v))
+                        })
+                    })
+                    .collect();
+
+                clock_tree::Pll {
+                    name: name.clone(),
+                    source,
+                    input,
+                    outputs: outputs.into_iter().collect(),
+                }
+            })
+            .collect();
+
+        let bus_names: BTreeSet<String> = en_rst
+            .values()
+            .map(|x| x.bus_clock.clone())
+            .chain(
+                muxes
+                    .iter()
+                    .flat_map(|m| &m.inputs)
+                    .filter(|v| regex!(r"^[PH]CLK\d*(_TIM)?$").is_match(v))
+                    .cloned(),
+            )
+            .collect();
+        let single_apb = prescalers.iter().any(|p| p.field == "PPRE");
+        let any_apb = prescalers.iter().any(|p| p.output.starts_with("PCLK"));
+        let ahb = ["HCLK", "SYS"].into_iter().find(|c| clocks.contains(*c));
+        let bus_clocks = bus_names
+            .into_iter()
+            .map(|name| {
+                let clock = if clocks.contains(&name) {
+                    Some(name.clone())
+                } else if let Some(m) = regex!(r"^(PCLK\d)(_TIM)?$").captures(&name) {
+                    let apb = m.get(1).unwrap().as_str();
+                    if clocks.contains(apb) {
+                        Some(apb.to_string())
+                    } else if single_apb {
+                        Some("PCLK1".to_string())
+                    } else if !any_apb {
+                        ahb.map(str::to_string)
+                    } else {
+                        None
+                    }
+                } else {
+                    ahb.map(str::to_string)
+                };
+                clock_tree::BusClock { name, clock }
+            })
+            .collect();
+
+        muxes.sort();
+        prescalers.sort();
+
+        Ok(ClockTree {
+            name: rcc_version.to_string(),
+            sources: sources.into_iter().collect(),
+            plls,
+            prescalers,
+            bus_clocks,
+            muxes,
+        })
+    }
+
+    pub fn clock_tree(&self, rcc_version: &str) -> Option<&ClockTree> {
+        self.rccs.get(rcc_version).map(|rcc| &rcc.clock_tree)
     }
 
     pub fn match_peri_clock(
@@ -395,7 +829,11 @@ impl ParsedRccs {
                         }
                     }
                 }
-                rcc::KernelClock::Mux(mux.mux.clone())
+                rcc::KernelClock::Mux(rcc::Mux {
+                    register: mux.mux.register.clone(),
+                    field: mux.mux.field.clone(),
+                    sources: mux.sources.clone(),
+                })
             }
             None => {
                 if peri_name.starts_with("USB") {
@@ -425,12 +863,56 @@ impl ParsedRccs {
             kernel_clock,
             enable: en_rst.enable.clone(),
             reset: en_rst.reset.clone(),
+            low_power_enable: en_rst.low_power_enable.clone(),
+            autonomous_enable: en_rst.autonomous_enable.clone(),
             stop_mode: en_rst.stop_mode.clone(),
+            low_power: None,
         })
     }
 }
 
-fn get_with_fallback<'a, T>(key: &str, map: &'a HashMap<String, T>, fallbacks: &[(&str, &[&str])]) -> Option<&'a T> {
+/// Strip the fixed divider or multiplier of a mux input, e.g. `HSI_DIV_2` is `HSI`.
+fn strip_fixed_divider(variant: &str) -> &str {
+    match regex!(r"^([A-Z0-9_]+)_(DIV|MUL)_\d+?$").captures(variant) {
+        Some(m) => m.get(1).unwrap().as_str(),
+        None => variant,
+    }
+}
+
+/// The PLL a register field belongs to, from the field name (`PLLSAIDIVQ`), the register name
+/// (`PLL2CFGR`) or the register or field array index (`PLLCFGR[1]`), and whether the field only
+/// applies to that PLL. `PLLCFGR.PLLSRC` on F4 is PLL1's register, but selects the input of all PLLs.
+fn pll_of(register: &str, field: &str, index: Option<u32>, pll_names: &BTreeSet<String>) -> Option<(String, bool)> {
+    let re = regex!(r"PLL(SAI|I2S)?(\d)?");
+    let m = [field, register]
+        .into_iter()
+        .find_map(|s| re.captures(s).filter(|m| m.get(1).is_some() || m.get(2).is_some()))
+        .or_else(|| [field, register].into_iter().find_map(|s| re.captures(s)))?;
+
+    let kind = m.get(1).map_or("", |k| k.as_str());
+    let specific = m.get(1).is_some() || m.get(2).is_some() || index.is_some();
+    let number = match (m.get(2), index) {
+        (Some(n), _) => n.as_str().parse().unwrap(),
+        (None, Some(index)) => index + 1,
+        (None, None) => 1,
+    };
+    let name = format!("PLL{kind}{number}");
+    pll_names.contains(&name).then_some((name, specific))
+}
+
+fn array_len(array: Option<&Array>) -> u32 {
+    match array {
+        None => 0,
+        Some(Array::Regular(array)) => array.len,
+        Some(Array::Cursed(array)) => array.offsets.len() as u32,
+    }
+}
+
+pub(crate) fn get_with_fallback<'a, T>(
+    key: &str,
+    map: &'a HashMap<String, T>,
+    fallbacks: &[(&str, &[&str])],
+) -> Option<&'a T> {
     if let Some(res) = map.get(key) {
         return Some(res);
     }
@@ -451,3 +933,65 @@ fn get_with_fallback<'a, T>(key: &str, map: &'a HashMap<String, T>, fallbacks: &
 
     None
 }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_tree_edges() {
+        let registers = Registers::load(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/registers")).unwrap();
+        let rccs = ParsedRccs::parse(&registers).unwrap();
+
+        // Every clock a prescaler, PLL or bus divides or runs from is produced somewhere in the tree.
+        for version in ["f4", "g4", "h5", "h7", "l4", "u5"] {
+            let tree = rccs.clock_tree(version).unwrap();
+            let produced: HashSet<String> = tree
+                .sources
+                .iter()
+                .chain(tree.muxes.iter().map(|m| &m.output))
+                .chain(tree.prescalers.iter().map(|p| &p.output))
+                .chain(tree.plls.iter().flat_map(|p| &p.outputs))
+                .cloned()
+                .chain(tree.plls.iter().map(|p| format!("{}_VCO", p.name)))
+                .collect();
+            let consumed = tree
+                .prescalers
+                .iter()
+                .map(|p| Some(&p.input))
+                .chain(tree.plls.iter().map(|p| p.input.as_ref()))
+                .chain(tree.bus_clocks.iter().map(|b| b.clock.as_ref()));
+            for clock in consumed {
+                let clock = clock.unwrap_or_else(|| panic!("rcc_{version}: unconnected clock"));
+                assert!(produced.contains(clock), "rcc_{version}: nothing produces {clock}");
+            }
+        }
+
+        let f4 = rccs.clock_tree("f4").unwrap();
+        let ppre1 = f4.prescalers.iter().find(|p| p.field == "PPRE1").unwrap();
+        assert_eq!((ppre1.input.as_str(), ppre1.output.as_str()), ("HCLK", "PCLK1"));
+        let sw = f4.muxes.iter().find(|m| m.field == "SW").unwrap();
+        assert_eq!(sw.output, "SYS");
+        assert!(sw.inputs.iter().any(|i| i == "PLL1_P"));
+        let pll1 = f4.plls.iter().find(|p| p.name == "PLL1").unwrap();
+        assert_eq!(pll1.input.as_deref(), Some("PLL_REF"));
+        let pllm = f4.prescalers.iter().find(|p| p.output == "PLL_REF").unwrap();
+        assert_eq!(pllm.input, "PLL_IN");
+
+        // H7 PLLs share the source mux, but each has its own DIVM and post-dividers.
+        let h7 = rccs.clock_tree("h7").unwrap();
+        let divm = h7
+            .prescalers
+            .iter()
+            .find(|p| p.field == "DIVM" && p.index == Some(1))
+            .unwrap();
+        assert_eq!((divm.input.as_str(), divm.output.as_str()), ("PLL_IN", "PLL2_REF"));
+        let divq = h7
+            .prescalers
+            .iter()
+            .find(|p| p.field == "PLLQ" && p.index == Some(2))
+            .unwrap();
+        assert_eq!((divq.input.as_str(), divq.output.as_str()), ("PLL3_VCO", "PLL3_Q"));
+        let hclk4 = h7.bus_clocks.iter().find(|b| b.name == "HCLK4").unwrap();
+        assert_eq!(hclk4.clock.as_deref(), Some("HCLK"));
+    }
+}
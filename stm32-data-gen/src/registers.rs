@@ -1,7 +1,7 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 use anyhow::anyhow;
-use chiptool::ir::IR;
+use chiptool::ir::{Array, BlockItemInner, IR};
 use chiptool::validate;
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
@@ -9,12 +9,18 @@ use rayon::prelude::*;
 pub struct Registers {
     /// Maps the file name (without the .yaml extension) to the IR object which is parsed from the mcu .svd file
     pub registers: HashMap<String, IR>,
-    pub blocks: HashMap<String, HashSet<String>>,
+    /// Maps the file name to the blocks it contains and the size of their address range in bytes
+    pub blocks: HashMap<String, HashMap<String, u32>>,
 }
 
 impl Registers {
     pub fn parse() -> Result<Self, anyhow::Error> {
-        let files = glob::glob("data/registers/*")?;
+        Self::load("data/registers")
+    }
+
+    /// Parse and validate every yaml file in `dir`.
+    pub fn load(dir: &str) -> Result<Self, anyhow::Error> {
+        let files = glob::glob(&format!("{dir}/*"))?;
 
         #[cfg(feature = "rayon")]
         let files = files.par_bridge();
@@ -58,12 +64,42 @@ impl Registers {
 
         let blocks = registers
             .iter()
-            .map(|(k, v)| (k.clone(), v.blocks.keys().cloned().collect()))
+            .map(|(k, v)| {
+                (
+                    k.clone(),
+                    v.blocks.keys().map(|b| (b.clone(), block_size(v, b))).collect(),
+                )
+            })
             .collect();
 
         Ok(Self { registers, blocks })
     }
 
+    /// Get the width of a field in any of the register files of a peripheral kind, e.g. `syscfg`.
+    pub fn field_bit_size(&self, kind: &str, fieldset: &str, field: &str) -> Option<u32> {
+        let prefix = format!("{kind}_");
+        self.registers
+            .iter()
+            .filter(|(name, _)| name.starts_with(&prefix))
+            .filter_map(|(_, ir)| ir.fieldsets.get(fieldset))
+            .flat_map(|fieldset| &fieldset.fields)
+            .find(|f| f.name == field)
+            .map(|f| f.bit_size)
+    }
+
+    /// Get the widest width of a field with this name in any fieldset of a register file, e.g. `EXTSEL`
+    /// in `adc_v2`.
+    pub fn max_field_bit_size(&self, file: &str, field: &str) -> Option<u32> {
+        self.registers
+            .get(file)?
+            .fieldsets
+            .values()
+            .flat_map(|fieldset| &fieldset.fields)
+            .filter(|f| f.name == field)
+            .map(|f| f.bit_size)
+            .max()
+    }
+
     pub fn write(&self) -> Result<(), anyhow::Error> {
         std::fs::create_dir_all("build/data/registers")?;
 
@@ -84,3 +120,41 @@ impl Registers {
         Ok(())
     }
 }
+
+/// Size of the address range covered by a block: the end of its last register, including arrays,
+/// nested blocks and the blocks it extends.
+fn block_size(ir: &IR, name: &str) -> u32 {
+    let Some(block) = ir.blocks.get(name) else {
+        return 0;
+    };
+
+    let mut end = block.extends.as_deref().map_or(0, |base| block_size(ir, base));
+    for item in &block.items {
+        let size = match &item.inner {
+            BlockItemInner::Register(r) => r.bit_size / 8,
+            BlockItemInner::Block(b) => block_size(ir, &b.block),
+        };
+        let last = match &item.array {
+            None => 0,
+            Some(Array::Regular(array)) => array.stride * array.len.saturating_sub(1),
+            Some(Array::Cursed(array)) => array.offsets.iter().copied().max().unwrap_or(0),
+        };
+        end = end.max(item.byte_offset + last + size);
+    }
+
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_size() {
+        let registers = Registers::load(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/registers")).unwrap();
+
+        // The last register is AFR[1] at 0x24
+        assert_eq!(registers.blocks["gpio_v2"]["GPIO"], 0x28);
+        assert_eq!(registers.blocks["gpio_v2"].get("RCC"), None);
+    }
+}
@@ -0,0 +1,172 @@
+use anyhow::Context;
+use serde::Deserialize;
+use stm32_data_serde::chip::core::peripheral::{Remap, RemapValue};
+
+use crate::registers::Registers;
+use crate::util::new_regex_map;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleValue {
+    value: u8,
+    pins: Vec<String>,
+}
+
+/// A rule of `data/remaps.yaml`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RemapRule {
+    /// Regex on `chip:peripheral`, or on `chip` for pad remaps
+    #[serde(rename = "match")]
+    pattern: String,
+    peripheral: String,
+    register: String,
+    field: String,
+    values: Vec<RuleValue>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Rules {
+    peripherals: Vec<RemapRule>,
+    #[serde(default)]
+    pads: Vec<RemapRule>,
+}
+
+impl RemapRule {
+    fn remap(&self, keep_pin: impl Fn(&str) -> bool) -> Remap {
+        Remap {
+            peripheral: self.peripheral.clone(),
+            register: self.register.clone(),
+            field: self.field.clone(),
+            values: self
+                .values
+                .iter()
+                .map(|v| RemapValue {
+                    value: v.value,
+                    pins: v.pins.iter().filter(|pin| keep_pin(pin)).cloned().collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+pub struct Remaps {
+    map: regex_map::RegexMap<RemapRule>,
+    pads: regex_map::RegexMap<RemapRule>,
+}
+
+impl Remaps {
+    /// Load the pin remaps of peripherals and package pads from `data/remaps.yaml`, and check that every
+    /// remap field exists in the `afio_xx`/`syscfg_xx` yaml files in `data/registers` and can hold its values.
+    pub fn parse(registers: &Registers) -> anyhow::Result<Self> {
+        Self::load("data/remaps.yaml", registers)
+    }
+
+    fn load(path: &str, registers: &Registers) -> anyhow::Result<Self> {
+        let rules: Rules = serde_yaml::from_str(&std::fs::read_to_string(path).context(path.to_string())?)
+            .context(path.to_string())?;
+
+        for rule in rules.peripherals.iter().chain(&rules.pads) {
+            let ctx = || format!("{}: {} {}.{}", path, rule.pattern, rule.register, rule.field);
+            regex::Regex::new(&rule.pattern).with_context(ctx)?;
+
+            let Some(bit_size) =
+                registers.field_bit_size(&rule.peripheral.to_ascii_lowercase(), &rule.register, &rule.field)
+            else {
+                anyhow::bail!("{}: field not found in the {} register files", ctx(), rule.peripheral);
+            };
+
+            if rule.values.is_empty() {
+                anyhow::bail!("{}: no values", ctx());
+            }
+            for v in &rule.values {
+                if u32::from(v.value) >> bit_size != 0 {
+                    anyhow::bail!("{}: value {} does not fit in {} bits", ctx(), v.value, bit_size);
+                }
+            }
+        }
+
+        Ok(Self {
+            map: new_regex_map(rules.peripherals.into_iter().map(|r| (r.pattern.clone(), r))),
+            pads: new_regex_map(rules.pads.into_iter().map(|r| (r.pattern.clone(), r))),
+        })
+    }
+
+    /// Get the pin remaps of a peripheral.
+    pub fn get(&self, mcu_name: &str, peripheral: &str) -> Vec<Remap> {
+        self.map
+            .get(&format!("{mcu_name}:{peripheral}"))
+            .map(|rule| rule.remap(|_| true))
+            .collect()
+    }
+
+    /// Get the remaps selecting which GPIO is bonded to a shared package pad, given the pins of the chip.
+    pub fn pads(&self, mcu_name: &str, pins: &[stm32_data_serde::chip::core::Pin]) -> Vec<Remap> {
+        self.pads
+            .get(mcu_name)
+            .map(|rule| rule.remap(|pin| pins.iter().any(|p| p.name == pin)))
+            .filter(|remap| remap.values.iter().any(|v| !v.pins.is_empty()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load() -> Remaps {
+        let registers = Registers::load(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/registers")).unwrap();
+        Remaps::load(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/remaps.yaml"), &registers).unwrap()
+    }
+
+    #[test]
+    fn test_remaps() {
+        let remaps = load();
+
+        let usart1 = remaps.get("STM32F103C8", "USART1");
+        assert_eq!(usart1.len(), 1);
+        assert_eq!(
+            (
+                usart1[0].peripheral.as_str(),
+                usart1[0].register.as_str(),
+                usart1[0].field.as_str()
+            ),
+            ("AFIO", "MAPR", "USART1_REMAP")
+        );
+        let pins: Vec<(u8, Vec<&str>)> = usart1[0]
+            .values
+            .iter()
+            .map(|v| (v.value, v.pins.iter().map(String::as_str).collect()))
+            .collect();
+        assert_eq!(
+            pins,
+            [
+                (0, vec!["PA11", "PA12", "PA8", "PA9", "PA10"]),
+                (1, vec!["PA11", "PA12", "PA8", "PB6", "PB7"]),
+            ]
+        );
+
+        // Patterns are anchored, TIM1 must not match TIM10
+        assert!(remaps.get("STM32F100RB", "TIM10").is_empty());
+
+        let pins = |names: &[&str]| -> Vec<stm32_data_serde::chip::core::Pin> {
+            names
+                .iter()
+                .map(|n| stm32_data_serde::chip::core::Pin { name: n.to_string() })
+                .collect()
+        };
+
+        // Only PA11 and PA9 are bonded, so PA12_RMP selects between two missing pins and is dropped
+        let pads = remaps.pads("STM32G031K8", &pins(&["PA9", "PA11"]));
+        assert_eq!(pads.len(), 1);
+        assert_eq!(pads[0].field, "PA11_RMP");
+
+        let pads = remaps.pads("STM32G031K8", &pins(&["PA9", "PA10", "PA11", "PA12"]));
+        let mut fields: Vec<&str> = pads.iter().map(|r| r.field.as_str()).collect();
+        fields.sort();
+        assert_eq!(fields, ["PA11_RMP", "PA12_RMP"]);
+
+        assert!(remaps.pads("STM32F103C8", &pins(&["PA11", "PA12"])).is_empty());
+    }
+}
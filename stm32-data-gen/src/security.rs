@@ -0,0 +1,203 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Context, bail};
+use chiptool::ir::{Array, IR};
+use lazy_regex::regex;
+use stm32_data_serde::chip::core::peripheral::{Security, security};
+
+use crate::rcc::get_with_fallback;
+use crate::registers::Registers;
+use crate::util::RuleSource;
+
+/// RIF indices of one RIFSC version, from `data/rifsc/<version>.yaml`.
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RifIndices {
+    source: RuleSource,
+    /// Peripheral name -> RISC index
+    risc: HashMap<String, u32>,
+    /// Peripheral name -> RIMC indices of its bus masters
+    #[serde(default)]
+    rimc: HashMap<String, Vec<u32>>,
+}
+
+impl RifIndices {
+    /// Load and check the indices against the sizes of the `RISC_SECCFGR` and `RIMC_ATTR` arrays.
+    fn load(path: &str, ir: &IR) -> anyhow::Result<Self> {
+        let indices: Self = serde_yaml::from_str(&std::fs::read_to_string(path).context(path.to_string())?)
+            .context(path.to_string())?;
+        indices.source.validate().context(path.to_string())?;
+        RuleSource::check_unverified(path, [&indices.source])?;
+
+        let array_len = |name: &str| {
+            ir.blocks
+                .values()
+                .flat_map(|b| &b.items)
+                .find(|i| i.name == name)
+                .and_then(|i| i.array.as_ref())
+                .map(|a| match a {
+                    Array::Regular(a) => a.len,
+                    Array::Cursed(a) => a.offsets.len() as u32,
+                })
+                .unwrap_or(1)
+        };
+        // RISC_SECCFGR is an array of 32-bit registers with one bit per index.
+        let risc = indices.risc.iter().map(|(peri, &index)| ("RISC", peri, index));
+        let rimc = indices
+            .rimc
+            .iter()
+            .flat_map(|(peri, masters)| masters.iter().map(move |&index| ("RIMC", peri, index)));
+        let mut seen = HashSet::new();
+        for (kind, peri, index) in risc.chain(rimc) {
+            let max = match kind {
+                "RISC" => array_len("RISC_SECCFGR") * 32,
+                _ => array_len("RIMC_ATTR"),
+            };
+            if index >= max {
+                bail!(
+                    "{}: {} index {} of {} is out of range (max {})",
+                    path,
+                    kind,
+                    index,
+                    peri,
+                    max - 1
+                );
+            }
+            if !seen.insert((kind, index)) {
+                bail!("{}: {} index {} is used more than once", path, kind, index);
+            }
+        }
+
+        Ok(indices)
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedSecurity {
+    /// GTZC version -> peripheral name -> TZSC bits
+    tzsc: HashMap<String, HashMap<String, Security>>,
+    /// RIFSC version -> RIF indices
+    rifsc: HashMap<String, RifIndices>,
+}
+
+impl ParsedSecurity {
+    /// Parse the TZSC bits from the `gtzc_xx` yaml files in `data/registers` and the RISC and RIMC
+    /// indices from `data/rifsc/<version>.yaml`.
+    pub fn parse(registers: &Registers) -> anyhow::Result<Self> {
+        Self::load(registers, "data/rifsc")
+    }
+
+    fn load(registers: &Registers, rifsc_dir: &str) -> anyhow::Result<Self> {
+        let mut tzsc = HashMap::new();
+        let mut rifsc = HashMap::new();
+
+        for (name, ir) in &registers.registers {
+            if let Some(version) = name.strip_prefix("gtzc_") {
+                let mut bits: HashMap<String, Security> = HashMap::new();
+                for (reg, body) in &ir.fieldsets {
+                    let Some(m) = regex!(r"^(?:TZSC_)?(SEC|PRIV)CFGR\d$").captures(reg) else {
+                        continue;
+                    };
+                    let kind = m.get(1).unwrap().as_str();
+
+                    for field in &body.fields {
+                        let Some(peri) = field.name.strip_suffix(kind) else {
+                            continue;
+                        };
+                        if peri.is_empty() {
+                            continue;
+                        }
+
+                        let val = security::Field {
+                            register: reg.clone(),
+                            field: field.name.clone(),
+                        };
+                        let entry = bits.entry(peri.to_string()).or_default();
+                        let slot = if kind == "SEC" {
+                            &mut entry.secure
+                        } else {
+                            &mut entry.privilege
+                        };
+                        if slot.replace(val).is_some() {
+                            bail!("gtzc: duplicate {} bit for {} in gtzc_{}", kind, peri, version);
+                        }
+                    }
+                }
+                tzsc.insert(version.to_string(), bits);
+            } else if let Some(version) = name.strip_prefix("rifsc_") {
+                // The RIF indices are not part of the register description, they come from the reference manual.
+                let path = format!("{rifsc_dir}/{version}.yaml");
+                rifsc.insert(version.to_string(), RifIndices::load(&path, ir)?);
+            }
+        }
+
+        Ok(Self { tzsc, rifsc })
+    }
+
+    pub fn match_peri_security(
+        &self,
+        gtzc_version: Option<&str>,
+        rifsc_version: Option<&str>,
+        peri_name: &str,
+    ) -> Option<Security> {
+        const FALLBACKS: &[(&str, &[&str])] = &[
+            ("ADC1", &["ADC12"]),
+            ("ADC2", &["ADC12"]),
+            ("USB", &["USBFS"]),
+            ("USB_OTG_FS", &["OTG"]),
+            ("USB_OTG_HS", &["OTG"]),
+            ("UCPD1", &["UCPD"]),
+        ];
+
+        let mut security = gtzc_version
+            .and_then(|v| self.tzsc.get(v))
+            .and_then(|bits| get_with_fallback(peri_name, bits, FALLBACKS))
+            .cloned()
+            .unwrap_or_default();
+
+        if let Some(indices) = rifsc_version.and_then(|v| self.rifsc.get(v)) {
+            security.risc_index = indices.risc.get(peri_name).copied();
+            security.rimc_indices = indices.rimc.get(peri_name).cloned().unwrap_or_default();
+        }
+
+        (security != Security::default()).then_some(security)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(register: &str, field: &str) -> Option<security::Field> {
+        Some(security::Field {
+            register: register.to_string(),
+            field: field.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_match_peri_security() {
+        let registers = Registers::load(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/registers")).unwrap();
+        let security = ParsedSecurity::load(&registers, concat!(env!("CARGO_MANIFEST_DIR"), "/../data/rifsc")).unwrap();
+
+        let usart1 = security.match_peri_security(Some("l5"), None, "USART1").unwrap();
+        assert_eq!(usart1.secure, field("TZSC_SECCFGR2", "USART1SEC"));
+        assert_eq!(usart1.privilege, field("TZSC_PRIVCFGR2", "USART1PRIV"));
+        assert_eq!(usart1.risc_index, None);
+
+        // H503 has no TrustZone, only the privilege bits
+        let usb = security.match_peri_security(Some("h503"), None, "USB").unwrap();
+        assert_eq!(usb.secure, None);
+        assert_eq!(usb.privilege, field("TZSC_PRIVCFGR2", "USBFSPRIV"));
+
+        assert_eq!(security.match_peri_security(Some("l5"), None, "GPIOA"), None);
+        assert_eq!(security.match_peri_security(None, None, "USART1"), None);
+
+        // N6 has no GTZC, the RIFSC indices come from `data/rifsc/n6.yaml`
+        let usart1 = security.match_peri_security(None, Some("n6"), "USART1").unwrap();
+        assert_eq!((usart1.secure, usart1.risc_index), (None, Some(34)));
+        assert!(usart1.rimc_indices.is_empty());
+        let ltdc = security.match_peri_security(None, Some("n6"), "LTDC").unwrap();
+        assert_eq!(ltdc.rimc_indices, [9, 10]);
+    }
+}
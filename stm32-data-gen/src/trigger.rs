@@ -1,961 +1,371 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::LazyLock;
 
-use regex::Regex;
+use anyhow::Context;
+use serde::Deserialize;
 
-use crate::util::new_regex_map;
+use crate::registers::Registers;
+use crate::util::{RuleSource, new_regex_map};
 
-#[derive(Clone)]
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Trigger {
-    pub signal: &'static str,
-    pub source: &'static str,
+    pub signal: String,
+    pub source: String,
+    /// Register field selecting this trigger and its value, from the rule's `fields`
+    #[serde(skip)]
+    pub field: Option<(String, u8)>,
+}
+
+/// A rule of `data/triggers.yaml`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TriggerRule {
+    /// Regex on `chip:peripheral`
+    #[serde(rename = "match")]
+    pattern: String,
+    pub source: RuleSource,
+    /// Signal prefix (e.g. `ADC_EXT_TRG`) -> register field selecting it by the signal number (e.g. `EXTSEL`)
+    #[serde(default)]
+    fields: BTreeMap<String, String>,
+    pub triggers: Vec<Trigger>,
 }
 
 pub struct Triggers {
-    map: regex_map::RegexMap<Vec<Trigger>>,
+    map: regex_map::RegexMap<TriggerRule>,
+    /// (register file, selector field) -> widest width of the field in the file
+    field_bits: HashMap<(String, String), u32>,
 }
 
 impl Triggers {
-    pub fn new() -> Self {
-        /// Regexmap where the key is mcu_name:peripheral and the value is the trigger list.
-        #[rustfmt::skip]
-        const PERIPHERAL_TRIGGER_RULES: &[(&str, &'static [Trigger])] = &[
-            (r"^STM32F0.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32F100.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32F101.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM8_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM5_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32F103.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM8_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM5_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32F105.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM5_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32F107.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM8_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM5_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32F1.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32F410.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM5_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32F4.*:ADC.*", &[
-                Trigger {signal: "ADC_EXT_TRG0", source: "TIM1_CH1" },
-                Trigger {signal: "ADC_EXT_TRG1", source: "TIM1_CH2" },
-                Trigger {signal: "ADC_EXT_TRG2", source: "TIM1_CH3" },
-                Trigger {signal: "ADC_EXT_TRG3", source: "TIM2_CH2" },
-                Trigger {signal: "ADC_EXT_TRG4", source: "TIM2_CH3" },
-                Trigger {signal: "ADC_EXT_TRG5", source: "TIM2_CH4" },
-                Trigger {signal: "ADC_EXT_TRG6", source: "TIM2_TRGO" },
-                Trigger {signal: "ADC_EXT_TRG7", source: "TIM3_CH1" },
-                Trigger {signal: "ADC_EXT_TRG8", source: "TIM3_TRGO" },
-                Trigger {signal: "ADC_EXT_TRG9", source: "TIM4_CH4" },
-                Trigger {signal: "ADC_EXT_TRG10", source: "TIM5_CH1" },
-                Trigger {signal: "ADC_EXT_TRG11", source: "TIM5_CH2" },
-                Trigger {signal: "ADC_EXT_TRG12", source: "TIM5_CH3" },
-                Trigger {signal: "ADC_EXT_TRG13", source: "TIM8_CH1" },
-                Trigger {signal: "ADC_EXT_TRG14", source: "TIM8_TRGO" },
-                Trigger {signal: "ADC_EXT_TRG15", source: "EXTI11_TRG" },
-                Trigger {signal: "ADC_JEXT_TRG0", source: "TIM1_CH4" },
-                Trigger {signal: "ADC_JEXT_TRG1", source: "TIM1_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG2", source: "TIM2_CH1" },
-                Trigger {signal: "ADC_JEXT_TRG3", source: "TIM2_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG4", source: "TIM3_CH2" },
-                Trigger {signal: "ADC_JEXT_TRG5", source: "TIM3_CH4" },
-                Trigger {signal: "ADC_JEXT_TRG6", source: "TIM4_CH1" },
-                Trigger {signal: "ADC_JEXT_TRG7", source: "TIM4_CH2" },
-                Trigger {signal: "ADC_JEXT_TRG8", source: "TIM4_CH3" },
-                Trigger {signal: "ADC_JEXT_TRG9", source: "TIM4_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG10", source: "TIM5_CH4" },
-                Trigger {signal: "ADC_JEXT_TRG11", source: "TIM5_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG12", source: "TIM8_CH2" },
-                Trigger {signal: "ADC_JEXT_TRG13", source: "TIM8_CH3" },
-                Trigger {signal: "ADC_JEXT_TRG14", source: "TIM8_CH4" },
-                Trigger {signal: "ADC_JEXT_TRG15", source: "EXTI15_TRG" },
-            ]),
-            (r"^STM32L4(5|6).*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM8_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM5_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32(F2|F4|F7|L4).*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM8_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM5_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32F3(01|02|18).*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32F3(03|28|58|98).*:DAC1", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM8_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM3_TRGO"}, // defaults to TIM8 but can be remapped to TIM3 with DAC_TRIG_RMP in SYSCFG_CFGR1
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32F3(03|28|58|98).*:DAC2", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32F37(3|8).*:DAC1", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM5_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32F37(3|8).*:DAC2", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM18_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32F334.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "HRTIM_DAC_TRG1"}, // Can be remapped to HRTIM_DACTRG1 using DAC1_TRIG3_RMP in SYSCFG_CFGR3.
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "HRTIM_DAC_TRG2"}, // Requires DAC_TRIG5_RMP set in SYSCFG_CFGR3.
-            ]),
-            (r"^STM32L0.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM3CH3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM21_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32L0.*:TIM2", &[
-                Trigger {signal: "TIMX_TI4_IN1", source: "COMP1_OUT"},
-                Trigger {signal: "TIMX_TI4_IN2", source: "COMP2_OUT"},
-            ]),
-            (r"^STM32L0.*:TIM21", &[
-                Trigger {signal: "TIMX_TI1_IN1", source: "COMP1_OUT"},
-                Trigger {signal: "TIMX_TI1_IN2", source: "COMP2_OUT"},
-            ]),
-            (r"^STM32L0.*:TIM22", &[
-                Trigger {signal: "TIMX_TI1_IN1", source: "COMP1_OUT"},
-                Trigger {signal: "TIMX_TI1_IN2", source: "COMP2_OUT"},
-            ]),
-            (r"^STM32L1.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG0", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM9_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32H7(2|3).*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG8", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG9", source: "HRTIM_DAC_TRG1"},
-                Trigger {signal: "DAC_CHX_TRG10", source: "HRTIM_DAC_TRG2"},
-                Trigger {signal: "DAC_CHX_TRG11", source: "LPTIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG12", source: "LPTIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG13", source: "EXTI9_TRG"},
-                Trigger {signal: "DAC_CHX_TRG14", source: "TIM23_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG15", source: "TIM24_TRGO"},
-            ]),
-            (r"^STM32H7(A|B).*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM5_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG7", source: "TIM8_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG8", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG9", source: "HRTIM_DAC_TRG1"},
-                Trigger {signal: "DAC_CHX_TRG10", source: "HRTIM_DAC_TRG2"},
-                Trigger {signal: "DAC_CHX_TRG11", source: "LPTIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG12", source: "LPTIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG13", source: "EXTI9_TRG"},
-                Trigger {signal: "DAC_CHX_TRG14", source: "LPTIM3_TRGO"},
-            ]),
-            (r"^STM32H7.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM5_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG7", source: "TIM8_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG8", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG9", source: "HRTIM_DAC_TRG1"},
-                Trigger {signal: "DAC_CHX_TRG10", source: "HRTIM_DAC_TRG2"},
-                Trigger {signal: "DAC_CHX_TRG11", source: "LPTIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG12", source: "LPTIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG13", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32(U5|U3).*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG8", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG11", source: "LPTIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG12", source: "LPTIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG13", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32(L4|L5).*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM5_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG7", source: "TIM8_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG8", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG11", source: "LPTIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG12", source: "LPTIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG13", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32H503.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG11", source: "LPTIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG12", source: "LPTIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG13", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32H5(6|7).*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM5_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG7", source: "TIM8_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG8", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG11", source: "LPTIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG12", source: "LPTIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG13", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32H5.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG11", source: "LPTIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG12", source: "LPTIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG13", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32G0.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG8", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG11", source: "LPTIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG12", source: "LPTIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG13", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32G0.*:ADC.*", &[
-                Trigger {signal: "ADC_EXT_TRG0", source: "TIM1_TRGO2"},
-                Trigger {signal: "ADC_EXT_TRG1", source: "TIM1_CC4"},
-                Trigger {signal: "ADC_EXT_TRG2", source: "TIM2_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG3", source: "TIM3_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG4", source: "TIM15_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG5", source: "TIM6_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG6", source: "TIM4_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG7", source: "EXTI11_TRG"},
-            ]),
-            (r"^STM32C0(91|92).*:ADC.*", &[
-                Trigger {signal: "ADC_EXT_TRG0", source: "TIM1_TRGO2"},
-                Trigger {signal: "ADC_EXT_TRG1", source: "TIM1_CC4"},
-                Trigger {signal: "ADC_EXT_TRG2", source: "TIM2_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG3", source: "TIM3_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG4", source: "TIM15_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG7", source: "EXTI11_TRG"},
-            ]),
-            (r"^STM32C0(51|71).*:ADC.*", &[
-                Trigger {signal: "ADC_EXT_TRG0", source: "TIM1_TRGO2"},
-                Trigger {signal: "ADC_EXT_TRG1", source: "TIM1_CC4"},
-                Trigger {signal: "ADC_EXT_TRG2", source: "TIM2_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG3", source: "TIM3_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG7", source: "EXTI11_TRG"},
-            ]),
-            (r"^STM32C0(11|31).*:ADC.*", &[
-                Trigger {signal: "ADC_EXT_TRG0", source: "TIM1_TRGO2"},
-                Trigger {signal: "ADC_EXT_TRG1", source: "TIM1_CC4"},
-                Trigger {signal: "ADC_EXT_TRG3", source: "TIM3_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG7", source: "EXTI11_TRG"},
-            ]),
-            (r"^STM32U0.*:TIM1", &[
-                Trigger {signal: "TIMX_TI1_IN1", source: "COMP1_OUT"},
-                Trigger {signal: "TIMX_TI2_IN1", source: "COMP2_OUT"},
-            ]),
-            (r"^STM32U0.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG8", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG11", source: "LPTIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG12", source: "LPTIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG14", source: "EXTI9_TRG"},
-            ]),
-            (r"^STM32H5.*:ADC(1|2|3)", &[
-                Trigger {signal: "ADC_EXT_TRG0", source: "TIM1_CC1"},
-                Trigger {signal: "ADC_EXT_TRG1", source: "TIM1_CC2"},
-                Trigger {signal: "ADC_EXT_TRG2", source: "TIM1_CC3"},
-                Trigger {signal: "ADC_EXT_TRG3", source: "TIM2_CC2"},
-                Trigger {signal: "ADC_EXT_TRG4", source: "TIM3_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG5", source: "TIM4_CC4"},
-                Trigger {signal: "ADC_EXT_TRG6", source: "EXTI11_TRG"},
-                Trigger {signal: "ADC_EXT_TRG7", source: "TIM8_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG8", source: "TIM8_TRGO2"},
-                Trigger {signal: "ADC_EXT_TRG9", source: "TIM1_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG10", source: "TIM1_TRGO2"},
-                Trigger {signal: "ADC_EXT_TRG11", source: "TIM2_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG12", source: "TIM4_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG13", source: "TIM6_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG14", source: "TIM15_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG15", source: "TIM3_CC4"},
-                Trigger {signal: "ADC_EXT_TRG16", source: "EXTI15_TRG"},
-                Trigger {signal: "ADC_EXT_TRG18", source: "LPTIM1_CH1"},
-                Trigger {signal: "ADC_EXT_TRG19", source: "LPTIM2_CH1"},
-                Trigger {signal: "ADC_JEXT_TRG0", source: "TIM1_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG1", source: "TIM1_CC4" },
-                Trigger {signal: "ADC_JEXT_TRG2", source: "TIM2_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG3", source: "TIM2_CC1" },
-                Trigger {signal: "ADC_JEXT_TRG4", source: "TIM3_CC4" },
-                Trigger {signal: "ADC_JEXT_TRG5", source: "TIM4_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG6", source: "EXTI15_TRG" },
-                Trigger {signal: "ADC_JEXT_TRG7", source: "TIM8_CC4" },
-                Trigger {signal: "ADC_JEXT_TRG8", source: "TIM1_TRGO2" },
-                Trigger {signal: "ADC_JEXT_TRG9", source: "TIM8_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG10", source: "TIM8_TRGO2" },
-                Trigger {signal: "ADC_JEXT_TRG11", source: "TIM3_CC3" },
-                Trigger {signal: "ADC_JEXT_TRG12", source: "TIM3_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG13", source: "TIM3_CC1" },
-                Trigger {signal: "ADC_JEXT_TRG14", source: "TIM6_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG15", source: "TIM15_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG18", source: "LPTIM1_CH1"},
-                Trigger {signal: "ADC_JEXT_TRG19", source: "LPTIM2_CH1"},
-            ]),
-            (r"^STM32G4.*:ADC(1|2)", &[
-                Trigger {signal: "ADC_EXT_TRG0", source: "TIM1_CC1"},
-                Trigger {signal: "ADC_EXT_TRG1", source: "TIM1_CC2"},
-                Trigger {signal: "ADC_EXT_TRG2", source: "TIM1_CC3"},
-                Trigger {signal: "ADC_EXT_TRG3", source: "TIM2_CC2"},
-                Trigger {signal: "ADC_EXT_TRG4", source: "TIM3_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG5", source: "TIM4_CC4"},
-                Trigger {signal: "ADC_EXT_TRG6", source: "EXTI11_TRG"},
-                Trigger {signal: "ADC_EXT_TRG7", source: "TIM8_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG8", source: "TIM8_TRGO2"},
-                Trigger {signal: "ADC_EXT_TRG9", source: "TIM1_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG10", source: "TIM1_TRGO2"},
-                Trigger {signal: "ADC_EXT_TRG11", source: "TIM2_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG12", source: "TIM4_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG13", source: "TIM6_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG14", source: "TIM15_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG15", source: "TIM3_CC4"},
-                Trigger {signal: "ADC_EXT_TRG16", source: "TIM20_TRGO"},
-                Trigger {signal: "ADC_EXT_TRG17", source: "TIM20_TRGO2"},
-                Trigger {signal: "ADC_EXT_TRG18", source: "TIM20_CC1"},
-                Trigger {signal: "ADC_EXT_TRG19", source: "TIM20_CC2"},
-                Trigger {signal: "ADC_EXT_TRG20", source: "TIM20_CC3"},
-                Trigger {signal: "ADC_EXT_TRG21", source: "HRTIM_ADC_TRG1"},
-                Trigger {signal: "ADC_EXT_TRG22", source: "HRTIM_ADC_TRG3"},
-                Trigger {signal: "ADC_EXT_TRG23", source: "HRTIM_ADC_TRG5"},
-                Trigger {signal: "ADC_EXT_TRG24", source: "HRTIM_ADC_TRG6"},
-                Trigger {signal: "ADC_EXT_TRG25", source: "HRTIM_ADC_TRG7"},
-                Trigger {signal: "ADC_EXT_TRG26", source: "HRTIM_ADC_TRG8"},
-                Trigger {signal: "ADC_EXT_TRG27", source: "HRTIM_ADC_TRG9"},
-                Trigger {signal: "ADC_EXT_TRG28", source: "HRTIM_ADC_TRG10"},
-                Trigger {signal: "ADC_EXT_TRG29", source: "LPTIMOUT"},
-                Trigger {signal: "ADC_EXT_TRG30", source: "TIM7_TRGO"},
-                Trigger {signal: "ADC_JEXT_TRG0", source: "TIM1_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG1", source: "TIM1_CC4" },
-                Trigger {signal: "ADC_JEXT_TRG2", source: "TIM2_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG3", source: "TIM2_CC1" },
-                Trigger {signal: "ADC_JEXT_TRG4", source: "TIM3_CC4" },
-                Trigger {signal: "ADC_JEXT_TRG5", source: "TIM4_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG6", source: "EXTI15_TRG" },
-                Trigger {signal: "ADC_JEXT_TRG7", source: "TIM8_CC4" },
-                Trigger {signal: "ADC_JEXT_TRG8", source: "TIM1_TRGO2" },
-                Trigger {signal: "ADC_JEXT_TRG9", source: "TIM8_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG10", source: "TIM8_TRGO2" },
-                Trigger {signal: "ADC_JEXT_TRG11", source: "TIM3_CC3" },
-                Trigger {signal: "ADC_JEXT_TRG12", source: "TIM3_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG13", source: "TIM3_CC1" },
-                Trigger {signal: "ADC_JEXT_TRG14", source: "TIM6_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG15", source: "TIM15_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG16", source: "TIM20_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG17", source: "TIM20_TRGO2" },
-                Trigger {signal: "ADC_JEXT_TRG18", source: "TIM20_CC4" },
-                Trigger {signal: "ADC_JEXT_TRG19", source: "HRTIM_ADC_TRG2" },
-                Trigger {signal: "ADC_JEXT_TRG20", source: "HRTIM_ADC_TRG4" },
-                Trigger {signal: "ADC_JEXT_TRG21", source: "HRTIM_ADC_TRG5" },
-                Trigger {signal: "ADC_JEXT_TRG22", source: "HRTIM_ADC_TRG6" },
-                Trigger {signal: "ADC_JEXT_TRG23", source: "HRTIM_ADC_TRG7" },
-                Trigger {signal: "ADC_JEXT_TRG24", source: "HRTIM_ADC_TRG8" },
-                Trigger {signal: "ADC_JEXT_TRG25", source: "HRTIM_ADC_TRG9" },
-                Trigger {signal: "ADC_JEXT_TRG26", source: "HRTIM_ADC_TRG10" },
-                Trigger {signal: "ADC_JEXT_TRG27", source: "TIM16_CC1" },
-                Trigger {signal: "ADC_JEXT_TRG29", source: "LPTIMOUT" },
-                Trigger {signal: "ADC_JEXT_TRG30", source: "TIM7_TRGO" },
-            ]),
-            (r"^STM32G4.*:ADC(3|4|5)", &[
-                Trigger {signal: "ADC_EXT_TRG0", source:  "TIM3_CC1" },
-                Trigger {signal: "ADC_EXT_TRG1", source:  "TIM2_CC3" },
-                Trigger {signal: "ADC_EXT_TRG2", source:  "TIM1_CC3" },
-                Trigger {signal: "ADC_EXT_TRG3", source:  "TIM8_CC1" },
-                Trigger {signal: "ADC_EXT_TRG4", source:  "TIM3_TRGO" },
-                Trigger {signal: "ADC_EXT_TRG5", source:  "EXTI2_TRG" },
-                Trigger {signal: "ADC_EXT_TRG6", source:  "TIM4_CC1" },
-                Trigger {signal: "ADC_EXT_TRG7", source:  "TIM8_TRGO" },
-                Trigger {signal: "ADC_EXT_TRG8", source:  "TIM8_TRGO2" },
-                Trigger {signal: "ADC_EXT_TRG9", source:  "TIM1_TRGO" },
-                Trigger {signal: "ADC_EXT_TRG10", source:  "TIM1_TRGO2" },
-                Trigger {signal: "ADC_EXT_TRG11", source:  "TIM2_TRGO" },
-                Trigger {signal: "ADC_EXT_TRG12", source:  "TIM4_TRGO" },
-                Trigger {signal: "ADC_EXT_TRG13", source:  "TIM6_TRGO" },
-                Trigger {signal: "ADC_EXT_TRG14", source:  "TIM15_TRGO" },
-                Trigger {signal: "ADC_EXT_TRG15", source:  "TIM2_CC1" },
-                Trigger {signal: "ADC_EXT_TRG16", source:  "TIM20_TRGO" },
-                Trigger {signal: "ADC_EXT_TRG17", source:  "TIM20_TRGO2" },
-                Trigger {signal: "ADC_EXT_TRG18", source:  "TIM20_CC1" },
-                Trigger {signal: "ADC_EXT_TRG19", source:  "HRTIM_ADC_TRG2" },
-                Trigger {signal: "ADC_EXT_TRG20", source:  "HRTIM_ADC_TRG4" },
-                Trigger {signal: "ADC_EXT_TRG21", source:  "HRTIM_ADC_TRG1" },
-                Trigger {signal: "ADC_EXT_TRG22", source:  "HRTIM_ADC_TRG3" },
-                Trigger {signal: "ADC_EXT_TRG23", source:  "HRTIM_ADC_TRG5" },
-                Trigger {signal: "ADC_EXT_TRG24", source:  "HRTIM_ADC_TRG6" },
-                Trigger {signal: "ADC_EXT_TRG25", source:  "HRTIM_ADC_TRG7" },
-                Trigger {signal: "ADC_EXT_TRG26", source:  "HRTIM_ADC_TRG8" },
-                Trigger {signal: "ADC_EXT_TRG27", source:  "HRTIM_ADC_TRG9" },
-                Trigger {signal: "ADC_EXT_TRG28", source:  "HRTIM_ADC_TRG10" },
-                Trigger {signal: "ADC_EXT_TRG29", source:  "LPTIMOUT" },
-                Trigger {signal: "ADC_EXT_TRG30", source:  "TIM7_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG0", source: "TIM1_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG1", source: "TIM1_CC4" },
-                Trigger {signal: "ADC_JEXT_TRG2", source: "TIM2_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG3", source: "TIM8_CC2" },
-                Trigger {signal: "ADC_JEXT_TRG4", source: "TIM4_CC3" },
-                Trigger {signal: "ADC_JEXT_TRG5", source: "TIM4_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG6", source: "TIM4_CC4" },
-                Trigger {signal: "ADC_JEXT_TRG7", source: "TIM8_CC4" },
-                Trigger {signal: "ADC_JEXT_TRG8", source: "TIM1_TRGO2" },
-                Trigger {signal: "ADC_JEXT_TRG9", source: "TIM8_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG10", source: "TIM8_TRGO2" },
-                Trigger {signal: "ADC_JEXT_TRG11", source: "TIM1_CC3" },
-                Trigger {signal: "ADC_JEXT_TRG12", source: "TIM3_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG13", source: "EXTI3_TRG" },
-                Trigger {signal: "ADC_JEXT_TRG14", source: "TIM6_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG15", source: "TIM15_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG16", source: "TIM20_TRGO" },
-                Trigger {signal: "ADC_JEXT_TRG17", source: "TIM20_TRGO2" },
-                Trigger {signal: "ADC_JEXT_TRG18", source: "TIM20_CC2" },
-                Trigger {signal: "ADC_JEXT_TRG19", source: "HRTIM_ADC_TRG2" },
-                Trigger {signal: "ADC_JEXT_TRG20", source: "HRTIM_ADC_TRG4" },
-                Trigger {signal: "ADC_JEXT_TRG21", source: "HRTIM_ADC_TRG5" },
-                Trigger {signal: "ADC_JEXT_TRG22", source: "HRTIM_ADC_TRG6" },
-                Trigger {signal: "ADC_JEXT_TRG23", source: "HRTIM_ADC_TRG7" },
-                Trigger {signal: "ADC_JEXT_TRG24", source: "HRTIM_ADC_TRG8" },
-                Trigger {signal: "ADC_JEXT_TRG25", source: "HRTIM_ADC_TRG9" },
-                Trigger {signal: "ADC_JEXT_TRG26", source: "HRTIM_ADC_TRG10" },
-                Trigger {signal: "ADC_JEXT_TRG27", source: "HRTIM_ADC_TRG1" },
-                Trigger {signal: "ADC_JEXT_TRG28", source: "HRTIM_ADC_TRG3" },
-                Trigger {signal: "ADC_JEXT_TRG29", source: "LPTIMOUT" },
-                Trigger {signal: "ADC_JEXT_TRG30", source: "TIM7_TRGO" },
-            ]),
-            (r"^STM32G4.*:DAC(1|4)", &[
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM8_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG15", source: "HRTIM_DAC_TRG1"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-                Trigger {signal: "DAC_CHX_TRG7", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG8", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG9", source: "HRTIM_DAC_RESET_TRG1"},
-                Trigger {signal: "DAC_CHX_TRG10", source: "HRTIM_DAC_RESET_TRG2"},
-                Trigger {signal: "DAC_CHX_TRG11", source: "HRTIM_DAC_RESET_TRG3"},
-                Trigger {signal: "DAC_CHX_TRG12", source: "HRTIM_DAC_RESET_TRG4"},
-                Trigger {signal: "DAC_CHX_TRG13", source: "HRTIM_DAC_RESET_TRG5"},
-                Trigger {signal: "DAC_CHX_TRG14", source: "HRTIM_DAC_RESET_TRG6"},
-                Trigger {signal: "DAC_INC_CHX_TRG1", source: "TIM8_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG3", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG6", source: "EXTI10_TRG"},
-                Trigger {signal: "DAC_INC_CHX_TRG7", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG8", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG9", source: "HRTIM_DAC_STEP_TRG1"},
-                Trigger {signal: "DAC_INC_CHX_TRG10", source: "HRTIM_DAC_STEP_TRG2"},
-                Trigger {signal: "DAC_INC_CHX_TRG11", source: "HRTIM_DAC_STEP_TRG3"},
-                Trigger {signal: "DAC_INC_CHX_TRG12", source: "HRTIM_DAC_STEP_TRG4"},
-                Trigger {signal: "DAC_INC_CHX_TRG13", source: "HRTIM_DAC_STEP_TRG5"},
-                Trigger {signal: "DAC_INC_CHX_TRG14", source: "HRTIM_DAC_STEP_TRG6"},
-            ]),
-            (r"^STM32G4.*:DAC2", &[
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM8_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG15", source: "HRTIM_DAC_TRG2"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-                Trigger {signal: "DAC_CHX_TRG7", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG8", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG9", source: "HRTIM_DAC_RESET_TRG1"},
-                Trigger {signal: "DAC_CHX_TRG10", source: "HRTIM_DAC_RESET_TRG2"},
-                Trigger {signal: "DAC_CHX_TRG11", source: "HRTIM_DAC_RESET_TRG3"},
-                Trigger {signal: "DAC_CHX_TRG12", source: "HRTIM_DAC_RESET_TRG4"},
-                Trigger {signal: "DAC_CHX_TRG13", source: "HRTIM_DAC_RESET_TRG5"},
-                Trigger {signal: "DAC_CHX_TRG14", source: "HRTIM_DAC_RESET_TRG6"},
-                Trigger {signal: "DAC_INC_CHX_TRG1", source: "TIM8_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG3", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG6", source: "EXTI10_TRG"},
-                Trigger {signal: "DAC_INC_CHX_TRG7", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG8", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG9", source: "HRTIM_DAC_STEP_TRG1"},
-                Trigger {signal: "DAC_INC_CHX_TRG10", source: "HRTIM_DAC_STEP_TRG2"},
-                Trigger {signal: "DAC_INC_CHX_TRG11", source: "HRTIM_DAC_STEP_TRG3"},
-                Trigger {signal: "DAC_INC_CHX_TRG12", source: "HRTIM_DAC_STEP_TRG4"},
-                Trigger {signal: "DAC_INC_CHX_TRG13", source: "HRTIM_DAC_STEP_TRG5"},
-                Trigger {signal: "DAC_INC_CHX_TRG14", source: "HRTIM_DAC_STEP_TRG6"},
-            ]),
-            (r"^STM32G4.*:DAC3", &[
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG15", source: "HRTIM_DAC_TRG3"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG3", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG6", source: "EXTI9_TRG"},
-                Trigger {signal: "DAC_CHX_TRG7", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG8", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG9", source: "HRTIM_DAC_RESET_TRG1"},
-                Trigger {signal: "DAC_CHX_TRG10", source: "HRTIM_DAC_RESET_TRG2"},
-                Trigger {signal: "DAC_CHX_TRG11", source: "HRTIM_DAC_RESET_TRG3"},
-                Trigger {signal: "DAC_CHX_TRG12", source: "HRTIM_DAC_RESET_TRG4"},
-                Trigger {signal: "DAC_CHX_TRG13", source: "HRTIM_DAC_RESET_TRG5"},
-                Trigger {signal: "DAC_CHX_TRG14", source: "HRTIM_DAC_RESET_TRG6"},
-                Trigger {signal: "DAC_INC_CHX_TRG1", source: "TIM1_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG2", source: "TIM7_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG3", source: "TIM15_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG4", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG5", source: "TIM4_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG6", source: "EXTI10_TRG"},
-                Trigger {signal: "DAC_INC_CHX_TRG7", source: "TIM6_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG8", source: "TIM3_TRGO"},
-                Trigger {signal: "DAC_INC_CHX_TRG9", source: "HRTIM_DAC_STEP_TRG1"},
-                Trigger {signal: "DAC_INC_CHX_TRG10", source: "HRTIM_DAC_STEP_TRG2"},
-                Trigger {signal: "DAC_INC_CHX_TRG11", source: "HRTIM_DAC_STEP_TRG3"},
-                Trigger {signal: "DAC_INC_CHX_TRG12", source: "HRTIM_DAC_STEP_TRG4"},
-                Trigger {signal: "DAC_INC_CHX_TRG13", source: "HRTIM_DAC_STEP_TRG5"},
-                Trigger {signal: "DAC_INC_CHX_TRG14", source: "HRTIM_DAC_STEP_TRG6"},
-            ]),
-            (r"^STM32G4.*:TIM1", &[
-                Trigger {signal: "TIMX_TI1_IN1", source: "COMP1_OUT"},
-                Trigger {signal: "TIMX_TI1_IN2", source: "COMP2_OUT"},
-                Trigger {signal: "TIMX_TI1_IN3", source: "COMP3_OUT"},
-                Trigger {signal: "TIMX_TI1_IN4", source: "COMP4_OUT"},
-            ]),
-            (r"^STM32WL.*:TIM1", &[
-                Trigger {signal: "TIMX_TI1_IN1", source: "COMP1_OUT"},
-            ]),
-            (r"^STM32WL.*:TIM2", &[
-                Trigger {signal: "TIMX_TI4_IN1", source: "COMP1_OUT"},
-                Trigger {signal: "TIMX_TI4_IN2", source: "COMP2_OUT"},
-                Trigger {signal: "TIMX_TI4_IN3", source: "COMP1_OUT_OR_COMP2_OUT"},
-            ]),
-            (r"^STM32WBA5[45].*:TIM1", &[
-                Trigger {signal: "TIMX_TI1_IN1", source: "COMP1_OUT"},
-                Trigger {signal: "TIMX_TI1_IN2", source: "COMP2_OUT"},
-                Trigger {signal: "TIMX_ITR_IN1", source: "TIM2_TRGO"},
-                Trigger {signal: "TIMX_ITR_IN2", source: "TIM3_TRGO"},
-                Trigger {signal: "TIMX_ITR_IN7", source: "TIM16_OC1"},
-                Trigger {signal: "TIMX_ITR_IN8", source: "TIM17_OC1"},
-                Trigger {signal: "TIMX_ETR_IN1", source: "COMP1_OUT"},
-                Trigger {signal: "TIMX_ETR_IN2", source: "COMP2_OUT"},
-                Trigger {signal: "TIMX_ETR_IN11", source: "ADC4_AWD1"},
-                Trigger {signal: "TIMX_ETR_IN12", source: "ADC4_AWD2"},
-                Trigger {signal: "TIMX_ETR_IN13", source: "ADC4_AWD3"},
-                Trigger {signal: "TIMX_BRK_IN1", source: "COMP1_OUT"},
-                Trigger {signal: "TIMX_BRK_IN2", source: "COMP2_OUT"},
-                Trigger {signal: "TIMX_BRK2_IN1", source: "COMP1_OUT"},
-                Trigger {signal: "TIMX_BRK2_IN2", source: "COMP2_OUT"},
-                Trigger {signal: "TIMX_SYS_BRK_IN0", source: "CORTEX_M33_LOCKUP"},
-                Trigger {signal: "TIMX_SYS_BRK_IN1", source: "PVD"},
-                Trigger {signal: "TIMX_SYS_BRK_IN2", source: "SRAM_PARITY_ERROR"},
-                Trigger {signal: "TIMX_SYS_BRK_IN3", source: "FLASH_ECC_ERROR"},
-                Trigger {signal: "TIMX_SYS_BRK_IN4", source: "HSE2_HSECSS"},
-                Trigger {signal: "TIMX_OCREF_CLR_IN0", source: "COMP1_OUT"},
-                Trigger {signal: "TIMX_OCREF_CLR_IN1", source: "COMP2_OUT"},
-            ]),
-            (r"^STM32WBA6[235].*:TIM1", &[
-                Trigger {signal: "TIMX_TI1_IN1", source: "COMP1_OUT"},
-                Trigger {signal: "TIMX_TI1_IN2", source: "COMP2_OUT"},
-                Trigger {signal: "TIMX_ITR_IN1", source: "TIM2_TRGO"},
-                Trigger {signal: "TIMX_ITR_IN2", source: "TIM3_TRGO"},
-                Trigger {signal: "TIMX_ETR_IN1", source: "COMP1_OUT"},
-                Trigger {signal: "TIMX_ETR_IN2", source: "COMP2_OUT"},
-                Trigger {signal: "TIMX_ETR_IN11", source: "ADC4_AWD1"},
-                Trigger {signal: "TIMX_ETR_IN12", source: "ADC4_AWD2"},
-                Trigger {signal: "TIMX_ETR_IN13", source: "ADC4_AWD3"},
-                Trigger {signal: "TIMX_BRK_IN1", source: "COMP1_OUT"},
-                Trigger {signal: "TIMX_BRK_IN2", source: "COMP2_OUT"},
-                Trigger {signal: "TIMX_BRK2_IN1", source: "COMP1_OUT"},
-                Trigger {signal: "TIMX_BRK2_IN2", source: "COMP2_OUT"},
-                Trigger {signal: "TIMX_SYS_BRK_IN0", source: "CORTEX_M33_LOCKUP"},
-                Trigger {signal: "TIMX_SYS_BRK_IN1", source: "PVD"},
-                Trigger {signal: "TIMX_SYS_BRK_IN2", source: "SRAM_PARITY_ERROR"},
-                Trigger {signal: "TIMX_SYS_BRK_IN3", source: "FLASH_ECC_ERROR"},
-                Trigger {signal: "TIMX_SYS_BRK_IN4", source: "HSE32_HSECSS"},
-                Trigger {signal: "TIMX_OCREF_CLR_IN0", source: "COMP1_OUT"},
-                Trigger {signal: "TIMX_OCREF_CLR_IN1", source: "COMP2_OUT"},
-            ]),
-            (r"^STM32WBA6[245].*:TIM1", &[
-                Trigger {signal: "TIMX_ITR_IN3", source: "TIM4_TRGO"},
-                Trigger {signal: "TIMX_ITR_IN7", source: "TIM16_OC1"},
-                Trigger {signal: "TIMX_ITR_IN8", source: "TIM17_OC1"},
-            ]),
-            (r"^STM32WBA6[235].*:LPTIM1", &[
-                Trigger {signal: "LPTIM_EXT_TRG0", source: "LPTIM1_ETR"},
-                Trigger {signal: "LPTIM_EXT_TRG1", source: "RTC_ALRA_TRG"},
-                Trigger {signal: "LPTIM_EXT_TRG2", source: "RTC_ALRB_TRG"},
-                Trigger {signal: "LPTIM_EXT_TRG3", source: "TAMP_TRG1"},
-                Trigger {signal: "LPTIM_EXT_TRG4", source: "TAMP_TRG2"},
-                Trigger {signal: "LPTIM_EXT_TRG6", source: "COMP1_OUT"},
-                Trigger {signal: "LPTIM_EXT_TRG7", source: "COMP2_OUT"},
-                Trigger {signal: "LPTIM_IN1_MUX0", source: "LPTIM1_IN1"},
-                Trigger {signal: "LPTIM_IN1_MUX1", source: "COMP1_OUT"},
-                Trigger {signal: "LPTIM_IN2_MUX0", source: "LPTIM1_IN2"},
-                Trigger {signal: "LPTIM_IN2_MUX1", source: "COMP2_OUT"},
-                Trigger {signal: "LPTIM_IC1_MUX0", source: "LPTIM1_CH1"},
-                Trigger {signal: "LPTIM_IC1_MUX1", source: "COMP1_OUT"},
-                Trigger {signal: "LPTIM_IC1_MUX2", source: "COMP2_OUT"},
-                Trigger {signal: "LPTIM_IC2_MUX0", source: "LPTIM1_CH2"},
-                Trigger {signal: "LPTIM_IC2_MUX1", source: "LSI"},
-                Trigger {signal: "LPTIM_IC2_MUX2", source: "LSE"},
-            ]),
-            (r"^STM32WBA6[235].*:LPTIM2", &[
-                Trigger {signal: "LPTIM_EXT_TRG0", source: "LPTIM2_ETR"},
-                Trigger {signal: "LPTIM_EXT_TRG1", source: "RTC_ALRA_TRG"},
-                Trigger {signal: "LPTIM_EXT_TRG2", source: "RTC_ALRB_TRG"},
-                Trigger {signal: "LPTIM_EXT_TRG3", source: "TAMP_TRG1"},
-                Trigger {signal: "LPTIM_EXT_TRG4", source: "GPDMA_CH0_TC"},
-                Trigger {signal: "LPTIM_EXT_TRG5", source: "GPDMA_CH1_TC"},
-                Trigger {signal: "LPTIM_EXT_TRG6", source: "COMP1_OUT"},
-                Trigger {signal: "LPTIM_EXT_TRG7", source: "COMP2_OUT"},
-                Trigger {signal: "LPTIM_IN1_MUX0", source: "LPTIM2_IN1"},
-                Trigger {signal: "LPTIM_IN1_MUX1", source: "COMP1_OUT"},
-                Trigger {signal: "LPTIM_IN2_MUX0", source: "LPTIM2_IN2"},
-                Trigger {signal: "LPTIM_IN2_MUX1", source: "COMP2_OUT"},
-                Trigger {signal: "LPTIM_IC1_MUX0", source: "LPTIM2_CH1"},
-                Trigger {signal: "LPTIM_IC1_MUX1", source: "COMP1_OUT"},
-                Trigger {signal: "LPTIM_IC1_MUX2", source: "COMP2_OUT"},
-                Trigger {signal: "LPTIM_IC2_MUX0", source: "LPTIM2_CH2"},
-                Trigger {signal: "LPTIM_IC2_MUX1", source: "HSI16_DIV256"},
-            ]),
-            (r"^STM32WBA6[235].*:SPI[12]", &[
-                Trigger {signal: "SPI_TRG0", source: "GPDMA_CH0_TC"},
-                Trigger {signal: "SPI_TRG1", source: "GPDMA_CH1_TC"},
-                Trigger {signal: "SPI_TRG2", source: "GPDMA_CH2_TC"},
-                Trigger {signal: "SPI_TRG3", source: "GPDMA_CH3_TC"},
-                Trigger {signal: "SPI_TRG4", source: "EXTI4_TRG"},
-                Trigger {signal: "SPI_TRG5", source: "EXTI9_TRG"},
-                Trigger {signal: "SPI_TRG6", source: "LPTIM1_CH1"},
-                Trigger {signal: "SPI_TRG7", source: "LPTIM2_CH1"},
-                Trigger {signal: "SPI_TRG8", source: "COMP1_OUT"},
-                Trigger {signal: "SPI_TRG9", source: "COMP2_OUT"},
-                Trigger {signal: "SPI_TRG10", source: "RTC_ALRA_TRG"},
-                Trigger {signal: "SPI_TRG11", source: "RTC_WUT_TRG"},
-            ]),
-            (r"^STM32WBA6[235].*:SPI3", &[
-                Trigger {signal: "SPI_TRG0", source: "GPDMA_CH0_TC"},
-                Trigger {signal: "SPI_TRG1", source: "GPDMA_CH1_TC"},
-                Trigger {signal: "SPI_TRG2", source: "GPDMA_CH2_TC"},
-                Trigger {signal: "SPI_TRG3", source: "GPDMA_CH3_TC"},
-                Trigger {signal: "SPI_TRG4", source: "EXTI4_TRG"},
-                Trigger {signal: "SPI_TRG5", source: "EXTI8_TRG"},
-                Trigger {signal: "SPI_TRG6", source: "LPTIM1_CH1"},
-                Trigger {signal: "SPI_TRG8", source: "COMP1_OUT"},
-                Trigger {signal: "SPI_TRG10", source: "RTC_ALRA_TRG"},
-                Trigger {signal: "SPI_TRG11", source: "RTC_WUT_TRG"},
-            ]),
-            (r"^STM32WBA6[235].*:USART[12]", &[
-                Trigger {signal: "USART_TRG0", source: "GPDMA_CH0_TC"},
-                Trigger {signal: "USART_TRG1", source: "GPDMA_CH1_TC"},
-                Trigger {signal: "USART_TRG2", source: "GPDMA_CH2_TC"},
-                Trigger {signal: "USART_TRG3", source: "GPDMA_CH3_TC"},
-                Trigger {signal: "USART_TRG4", source: "EXTI6_TRG"},
-                Trigger {signal: "USART_TRG5", source: "EXTI9_TRG"},
-                Trigger {signal: "USART_TRG6", source: "LPTIM1_CH1"},
-                Trigger {signal: "USART_TRG7", source: "LPTIM2_CH1"},
-                Trigger {signal: "USART_TRG8", source: "COMP1_OUT"},
-                Trigger {signal: "USART_TRG9", source: "COMP2_OUT"},
-                Trigger {signal: "USART_TRG10", source: "RTC_ALRA_TRG"},
-                Trigger {signal: "USART_TRG11", source: "RTC_WUT_TRG"},
-            ]),
-            (r"^STM32WBA5[45].*:LPTIM1", &[
-                Trigger {signal: "LPTIM_EXT_TRG0", source: "LPTIM1_ETR"},
-                Trigger {signal: "LPTIM_EXT_TRG1", source: "RTC_ALRA_TRG"},
-                Trigger {signal: "LPTIM_EXT_TRG2", source: "RTC_ALRB_TRG"},
-                Trigger {signal: "LPTIM_EXT_TRG3", source: "TAMP_TRG1"},
-                Trigger {signal: "LPTIM_EXT_TRG4", source: "TAMP_TRG2"},
-                Trigger {signal: "LPTIM_EXT_TRG6", source: "COMP1_OUT"},
-                Trigger {signal: "LPTIM_EXT_TRG7", source: "COMP2_OUT"},
-                Trigger {signal: "LPTIM_IN1_MUX0", source: "LPTIM1_IN1"},
-                Trigger {signal: "LPTIM_IN1_MUX1", source: "COMP1_OUT"},
-                Trigger {signal: "LPTIM_IN2_MUX0", source: "LPTIM1_IN2"},
-                Trigger {signal: "LPTIM_IN2_MUX1", source: "COMP2_OUT"},
-                Trigger {signal: "LPTIM_IC1_MUX0", source: "LPTIM1_CH1"},
-                Trigger {signal: "LPTIM_IC1_MUX1", source: "COMP1_OUT"},
-                Trigger {signal: "LPTIM_IC1_MUX2", source: "COMP2_OUT"},
-                Trigger {signal: "LPTIM_IC2_MUX0", source: "LPTIM1_CH2"},
-                Trigger {signal: "LPTIM_IC2_MUX1", source: "LSI"},
-                Trigger {signal: "LPTIM_IC2_MUX2", source: "LSE"},
-            ]),
-            (r"^STM32WBA5[45].*:LPTIM2", &[
-                Trigger {signal: "LPTIM_EXT_TRG0", source: "LPTIM2_ETR"},
-                Trigger {signal: "LPTIM_EXT_TRG1", source: "RTC_ALRA_TRG"},
-                Trigger {signal: "LPTIM_EXT_TRG2", source: "RTC_ALRB_TRG"},
-                Trigger {signal: "LPTIM_EXT_TRG3", source: "TAMP_TRG1"},
-                Trigger {signal: "LPTIM_EXT_TRG4", source: "GPDMA_CH0_TC"},
-                Trigger {signal: "LPTIM_EXT_TRG5", source: "GPDMA_CH4_TC"},
-                Trigger {signal: "LPTIM_EXT_TRG6", source: "COMP1_OUT"},
-                Trigger {signal: "LPTIM_EXT_TRG7", source: "COMP2_OUT"},
-                Trigger {signal: "LPTIM_IN1_MUX0", source: "LPTIM2_IN1"},
-                Trigger {signal: "LPTIM_IN1_MUX1", source: "COMP1_OUT"},
-                Trigger {signal: "LPTIM_IN2_MUX0", source: "LPTIM2_IN2"},
-                Trigger {signal: "LPTIM_IN2_MUX1", source: "COMP2_OUT"},
-                Trigger {signal: "LPTIM_IC1_MUX0", source: "LPTIM2_CH1"},
-                Trigger {signal: "LPTIM_IC1_MUX1", source: "COMP1_OUT"},
-                Trigger {signal: "LPTIM_IC1_MUX2", source: "COMP2_OUT"},
-                Trigger {signal: "LPTIM_IC2_MUX0", source: "LPTIM2_CH2"},
-                Trigger {signal: "LPTIM_IC2_MUX1", source: "HSI16_DIV256"},
-            ]),
-            (r"^STM32WBA5[45].*:SPI1", &[
-                Trigger {signal: "SPI_TRG0", source: "GPDMA_CH0_TC"},
-                Trigger {signal: "SPI_TRG1", source: "GPDMA_CH1_TC"},
-                Trigger {signal: "SPI_TRG2", source: "GPDMA_CH2_TC"},
-                Trigger {signal: "SPI_TRG3", source: "GPDMA_CH3_TC"},
-                Trigger {signal: "SPI_TRG4", source: "EXTI4_TRG"},
-                Trigger {signal: "SPI_TRG5", source: "EXTI9_TRG"},
-                Trigger {signal: "SPI_TRG6", source: "LPTIM1_CH1"},
-                Trigger {signal: "SPI_TRG7", source: "LPTIM2_CH1"},
-                Trigger {signal: "SPI_TRG8", source: "COMP1_OUT"},
-                Trigger {signal: "SPI_TRG9", source: "COMP2_OUT"},
-                Trigger {signal: "SPI_TRG10", source: "RTC_ALRA_TRG"},
-                Trigger {signal: "SPI_TRG11", source: "RTC_WUT_TRG"},
-            ]),
-            (r"^STM32WBA5[45].*:SPI3", &[
-                Trigger {signal: "SPI_TRG0", source: "GPDMA_CH0_TC"},
-                Trigger {signal: "SPI_TRG1", source: "GPDMA_CH1_TC"},
-                Trigger {signal: "SPI_TRG2", source: "GPDMA_CH2_TC"},
-                Trigger {signal: "SPI_TRG3", source: "GPDMA_CH3_TC"},
-                Trigger {signal: "SPI_TRG4", source: "EXTI4_TRG"},
-                Trigger {signal: "SPI_TRG5", source: "EXTI8_TRG"},
-                Trigger {signal: "SPI_TRG6", source: "LPTIM1_CH1"},
-                Trigger {signal: "SPI_TRG8", source: "COMP1_OUT"},
-                Trigger {signal: "SPI_TRG10", source: "RTC_ALRA_TRG"},
-                Trigger {signal: "SPI_TRG11", source: "RTC_WUT_TRG"},
-            ]),
-            (r"^STM32WBA5[45].*:USART[12]", &[
-                Trigger {signal: "USART_TRG0", source: "GPDMA_CH0_TC"},
-                Trigger {signal: "USART_TRG1", source: "GPDMA_CH1_TC"},
-                Trigger {signal: "USART_TRG2", source: "GPDMA_CH2_TC"},
-                Trigger {signal: "USART_TRG3", source: "GPDMA_CH3_TC"},
-                Trigger {signal: "USART_TRG4", source: "EXTI6_TRG"},
-                Trigger {signal: "USART_TRG5", source: "EXTI9_TRG"},
-                Trigger {signal: "USART_TRG6", source: "LPTIM1_CH1"},
-                Trigger {signal: "USART_TRG7", source: "LPTIM2_CH1"},
-                Trigger {signal: "USART_TRG8", source: "COMP1_OUT"},
-                Trigger {signal: "USART_TRG9", source: "COMP2_OUT"},
-                Trigger {signal: "USART_TRG10", source: "RTC_ALRA_TRG"},
-                Trigger {signal: "USART_TRG11", source: "RTC_WUT_TRG"},
-            ]),
-            (r"^STM32WBA6[235].*:I2C[124]", &[
-                Trigger {signal: "I2C_TRG0", source: "GPDMA_CH0_TC"},
-                Trigger {signal: "I2C_TRG1", source: "GPDMA_CH1_TC"},
-                Trigger {signal: "I2C_TRG2", source: "GPDMA_CH2_TC"},
-                Trigger {signal: "I2C_TRG3", source: "GPDMA_CH3_TC"},
-                Trigger {signal: "I2C_TRG4", source: "EXTI5_TRG"},
-                Trigger {signal: "I2C_TRG5", source: "EXTI9_TRG"},
-                Trigger {signal: "I2C_TRG6", source: "LPTIM1_CH1"},
-                Trigger {signal: "I2C_TRG7", source: "LPTIM2_CH1"},
-                Trigger {signal: "I2C_TRG8", source: "COMP1_OUT"},
-                Trigger {signal: "I2C_TRG9", source: "COMP2_OUT"},
-                Trigger {signal: "I2C_TRG10", source: "RTC_ALRA_TRG"},
-                Trigger {signal: "I2C_TRG11", source: "RTC_WUT_TRG"},
-            ]),
-            (r"^STM32WBA6[235].*:I2C3", &[
-                Trigger {signal: "I2C_TRG0", source: "GPDMA_CH0_TC"},
-                Trigger {signal: "I2C_TRG1", source: "GPDMA_CH1_TC"},
-                Trigger {signal: "I2C_TRG2", source: "GPDMA_CH2_TC"},
-                Trigger {signal: "I2C_TRG3", source: "GPDMA_CH3_TC"},
-                Trigger {signal: "I2C_TRG4", source: "EXTI5_TRG"},
-                Trigger {signal: "I2C_TRG5", source: "EXTI8_TRG"},
-                Trigger {signal: "I2C_TRG6", source: "LPTIM1_CH1"},
-                Trigger {signal: "I2C_TRG8", source: "COMP1_OUT"},
-                Trigger {signal: "I2C_TRG9", source: "COMP2_OUT"},
-                Trigger {signal: "I2C_TRG10", source: "RTC_ALRA_TRG"},
-                Trigger {signal: "I2C_TRG11", source: "RTC_WUT_TRG"},
-            ]),
-            (r"^STM32WBA5[45].*:I2C1", &[
-                Trigger {signal: "I2C_TRG0", source: "GPDMA_CH0_TC"},
-                Trigger {signal: "I2C_TRG1", source: "GPDMA_CH1_TC"},
-                Trigger {signal: "I2C_TRG2", source: "GPDMA_CH2_TC"},
-                Trigger {signal: "I2C_TRG3", source: "GPDMA_CH3_TC"},
-                Trigger {signal: "I2C_TRG4", source: "EXTI5_TRG"},
-                Trigger {signal: "I2C_TRG5", source: "EXTI9_TRG"},
-                Trigger {signal: "I2C_TRG6", source: "LPTIM1_CH1"},
-                Trigger {signal: "I2C_TRG7", source: "LPTIM2_CH1"},
-                Trigger {signal: "I2C_TRG8", source: "COMP1_OUT"},
-                Trigger {signal: "I2C_TRG9", source: "COMP2_OUT"},
-                Trigger {signal: "I2C_TRG10", source: "RTC_ALRA_TRG"},
-                Trigger {signal: "I2C_TRG11", source: "RTC_WUT_TRG"},
-            ]),
-            (r"^STM32WBA5[45].*:I2C3", &[
-                Trigger {signal: "I2C_TRG0", source: "GPDMA_CH0_TC"},
-                Trigger {signal: "I2C_TRG1", source: "GPDMA_CH1_TC"},
-                Trigger {signal: "I2C_TRG2", source: "GPDMA_CH2_TC"},
-                Trigger {signal: "I2C_TRG3", source: "GPDMA_CH3_TC"},
-                Trigger {signal: "I2C_TRG4", source: "EXTI5_TRG"},
-                Trigger {signal: "I2C_TRG5", source: "EXTI8_TRG"},
-                Trigger {signal: "I2C_TRG6", source: "LPTIM1_CH1"},
-                Trigger {signal: "I2C_TRG8", source: "COMP1_OUT"},
-                Trigger {signal: "I2C_TRG9", source: "COMP2_OUT"},
-                Trigger {signal: "I2C_TRG10", source: "RTC_ALRA_TRG"},
-                Trigger {signal: "I2C_TRG11", source: "RTC_WUT_TRG"},
-            ]),
-            (r"^STM32WBA[56].*:ADC4", &[
-                Trigger {signal: "ADC_TRG0", source: "TIM1_TRGO2"},
-                Trigger {signal: "ADC_TRG1", source: "TIM1_OC4"},
-                Trigger {signal: "ADC_TRG2", source: "TIM2_TRGO"},
-                Trigger {signal: "ADC_TRG5", source: "LPTIM1_CH1"},
-                Trigger {signal: "ADC_TRG7", source: "EXTI15_TRG"},
-            ]),
-            (r"^STM32WL.*:DAC.*", &[
-                Trigger {signal: "DAC_CHX_TRG1", source: "TIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG2", source: "TIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG11", source: "LPTIM1_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG12", source: "LPTIM2_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG13", source: "LPTIM3_TRGO"},
-                Trigger {signal: "DAC_CHX_TRG14", source: "EXTI9_TRG"},
-            ]),
-        ];
-
-        let trigger_expr = Regex::new(r"(?m)(.+?)(\d+)").unwrap();
-
-        for (expr, triggers) in PERIPHERAL_TRIGGER_RULES {
-            let mut trigger_sets: HashMap<String, HashSet<&str>> = HashMap::new();
-
-            for trigger in *triggers {
-                let matches = trigger_expr.captures(trigger.signal).unwrap();
-                let trigger_set = trigger_sets.entry((&matches[1]).to_string()).or_insert(HashSet::new());
-
-                if !trigger_set.insert(trigger.source) || trigger.source != trigger.source.to_uppercase() {
-                    panic!(
-                        "trigger: failed to validate rules for expr {} (source: {})",
-                        *expr, trigger.source
+    /// Load the trigger connections of peripherals from `data/triggers.yaml`. The first matching rule wins.
+    ///
+    /// Selector fields are checked against the register version of each peripheral in [`Self::check`].
+    pub fn parse(registers: &Registers) -> anyhow::Result<Self> {
+        Self::load("data/triggers.yaml", registers)
+    }
+
+    fn load(path: &str, registers: &Registers) -> anyhow::Result<Self> {
+        let mut rules: Vec<TriggerRule> =
+            serde_yaml::from_str(&std::fs::read_to_string(path).context(path.to_string())?)
+                .context(path.to_string())?;
+
+        let trigger_expr = lazy_regex::regex!(r"(?m)(.+?)(\d+)");
+
+        for rule in &mut rules {
+            regex::Regex::new(&rule.pattern).with_context(|| format!("{}: {}", path, rule.pattern))?;
+            rule.source
+                .validate()
+                .with_context(|| format!("{}: {}", path, rule.pattern))?;
+
+            let mut trigger_sets: HashMap<&str, HashSet<&str>> = HashMap::new();
+
+            for trigger in &mut rule.triggers {
+                let Some(matches) = trigger_expr.captures(&trigger.signal) else {
+                    anyhow::bail!("{}: {}: invalid signal {}", path, rule.pattern, trigger.signal);
+                };
+                let prefix = matches.get(1).unwrap().as_str();
+
+                if let Some(field) = rule.fields.get(prefix) {
+                    trigger.field = Some((field.clone(), matches[2].parse()?));
+                }
+
+                let trigger_set = trigger_sets.entry(prefix).or_default();
+
+                if !trigger_set.insert(&trigger.source) || trigger.source != trigger.source.to_uppercase() {
+                    anyhow::bail!(
+                        "{}: failed to validate rules for expr {} (source: {})",
+                        path,
+                        rule.pattern,
+                        trigger.source
                     );
                 }
             }
-        }
 
-        Self {
-            map: new_regex_map(PERIPHERAL_TRIGGER_RULES.iter().map(|(k, v)| (k, v.to_vec()))),
+            if let Some(prefix) = rule.fields.keys().find(|p| !trigger_sets.contains_key(p.as_str())) {
+                anyhow::bail!("{}: {}: no trigger signal starts with {}", path, rule.pattern, prefix);
+            }
         }
+
+        RuleSource::check_unverified(path, rules.iter().map(|r| &r.source))?;
+
+        let fields: HashSet<&String> = rules.iter().flat_map(|r| r.fields.values()).collect();
+        let field_bits = registers
+            .registers
+            .keys()
+            .flat_map(|file| {
+                fields.iter().filter_map(move |&field| {
+                    let bits = registers.max_field_bit_size(file, field)?;
+                    Some(((file.clone(), field.clone()), bits))
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            map: new_regex_map(rules.into_iter().map(|r| (r.pattern.clone(), r))),
+            field_bits,
+        })
     }
 
-    /// Get the stop mode limit for a peripheral based on the MCU and peripheral name.
-    /// Determines the lowest possible stop mode when a peripheral is enabled.
+    /// Get the trigger rule of a peripheral based on the MCU and peripheral name.
     ///
     /// Parameters:
     /// - mcu_name: the full name of the MCU (e.g., "STM32WB55RG")
     /// - peripheral: the name of the peripheral (e.g., "USART1")
-    pub fn peripheral_trigger_info(&self, mcu_name: &str, peripheral: &str) -> Option<&[Trigger]> {
-        self.map.get(&format!("{mcu_name}:{peripheral}")).next().map(|v| &**v)
+    pub fn peripheral_trigger_info(&self, mcu_name: &str, peripheral: &str) -> Option<&TriggerRule> {
+        self.map.get(&format!("{mcu_name}:{peripheral}")).next()
+    }
+
+    /// Check that the selector field of every trigger of a peripheral exists in its register version and
+    /// is wide enough for the value.
+    pub fn check(&self, mcu_name: &str, peripheral: &stm32_data_serde::chip::core::Peripheral) -> anyhow::Result<()> {
+        let registers = peripheral.registers.as_ref();
+
+        for trigger in &peripheral.triggers {
+            let (Some(field), Some(value)) = (&trigger.field, trigger.value) else {
+                continue;
+            };
+            let Some(registers) = registers else {
+                anyhow::bail!(
+                    "{}: {} selects {} with {} but has no registers",
+                    mcu_name,
+                    peripheral.name,
+                    trigger.signal,
+                    field
+                );
+            };
+            let file = format!("{}_{}", registers.kind, registers.version);
+            match self.field_bits.get(&(file.clone(), field.clone())) {
+                None => anyhow::bail!("{}: {}: {} not found in {}", mcu_name, peripheral.name, field, file),
+                Some(&bits) if u32::from(value) >= 1 << bits => anyhow::bail!(
+                    "{}: {}: {} does not fit in {} of {} ({} bits)",
+                    mcu_name,
+                    peripheral.name,
+                    trigger.signal,
+                    field,
+                    file,
+                    bits
+                ),
+                Some(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Timer blocks with a slave mode controller, and so with internal trigger inputs.
+const SLAVE_TIMER_BLOCKS: &[&str] = &["TIM_2CH", "TIM_2CH_CMP", "TIM_ADV", "TIM_GP16", "TIM_GP32"];
+
+/// Timers, matched against `CHIP:PERIPHERAL`, whose internal trigger table in `data/timer_itr.yaml` is
+/// still to be written. Every other timer with a slave mode controller must have one.
+#[rustfmt::skip]
+static MISSING_TIMER_ITR: LazyLock<regex_map::RegexMap<&str>> = LazyLock::new(|| {
+    const MISSING: &[&str] = &[
+        "STM32F100.*:TIM(4|5|12|15)",
+        "STM32C0.*:.*",
+        "STM32F0.*:.*",
+        "STM32F3.*:.*",
+        "STM32G0.*:.*",
+        "STM32H7[RS].*:.*",
+        "STM32L0.*:.*",
+        "STM32L1.*:.*",
+        "STM32L5.*:.*",
+        "STM32N6.*:.*",
+        "STM32U0.*:.*",
+        "STM32U3.*:.*",
+        "STM32WB.*:.*",
+        "STM32WL.*:.*",
+    ];
+    new_regex_map(MISSING.iter().map(|&m| (m, m)))
+});
+
+/// A rule of `data/timer_itr.yaml`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InternalTriggerRule {
+    /// Regex on `chip:peripheral`
+    #[serde(rename = "match")]
+    pattern: String,
+    pub source: RuleSource,
+    /// `ITRx` input number -> signal connected to it
+    pub itr: BTreeMap<u8, String>,
+}
+
+pub struct InternalTriggers {
+    map: regex_map::RegexMap<InternalTriggerRule>,
+}
+
+impl InternalTriggers {
+    /// Load the internal trigger connections of timers from `data/timer_itr.yaml`. The first matching rule wins.
+    pub fn parse() -> anyhow::Result<Self> {
+        Self::load("data/timer_itr.yaml")
+    }
+
+    fn load(path: &str) -> anyhow::Result<Self> {
+        let rules: Vec<InternalTriggerRule> =
+            serde_yaml::from_str(&std::fs::read_to_string(path).context(path.to_string())?)
+                .context(path.to_string())?;
+
+        let source_expr = lazy_regex::regex!(r"^[A-Z][A-Z0-9]*_[A-Z0-9_]+$");
+
+        for rule in &rules {
+            regex::Regex::new(&rule.pattern).with_context(|| format!("{}: {}", path, rule.pattern))?;
+            rule.source
+                .validate()
+                .with_context(|| format!("{}: {}", path, rule.pattern))?;
+
+            for (&itr, source) in &rule.itr {
+                if itr > 15 {
+                    anyhow::bail!("{}: {}: there is no ITR{}", path, rule.pattern, itr);
+                }
+                if !source_expr.is_match(source) {
+                    anyhow::bail!(
+                        "{}: {}: ITR{} source {} is not PERIPHERAL_SIGNAL",
+                        path,
+                        rule.pattern,
+                        itr,
+                        source
+                    );
+                }
+            }
+        }
+        RuleSource::check_unverified(path, rules.iter().map(|r| &r.source))?;
+
+        Ok(Self {
+            map: new_regex_map(rules.into_iter().map(|r| (r.pattern.clone(), r))),
+        })
+    }
+
+    /// Get the internal trigger rule of a timer based on the MCU and peripheral name.
+    pub fn peripheral_itr_info(&self, mcu_name: &str, peripheral: &str) -> Option<&InternalTriggerRule> {
+        self.map.get(&format!("{mcu_name}:{peripheral}")).next()
+    }
+
+    /// Check that a timer with a slave mode controller has an internal trigger table, unless it is listed
+    /// in `MISSING_TIMER_ITR`.
+    pub fn check(&self, mcu_name: &str, peripheral: &stm32_data_serde::chip::core::Peripheral) -> anyhow::Result<()> {
+        let Some(registers) = &peripheral.registers else {
+            return Ok(());
+        };
+        if registers.kind != "timer" || !SLAVE_TIMER_BLOCKS.contains(&registers.block.as_str()) {
+            return Ok(());
+        }
+
+        let key = format!("{}:{}", mcu_name, peripheral.name);
+        let has_rule = self.map.get(&key).next().is_some();
+        match (has_rule, MISSING_TIMER_ITR.get(&key).next()) {
+            (false, None) => anyhow::bail!(
+                "{}: {} has no internal trigger table, add it to data/timer_itr.yaml",
+                mcu_name,
+                peripheral.name
+            ),
+            (true, Some(missing)) => anyhow::bail!(
+                "{}: {} has an internal trigger table, remove {} from MISSING_TIMER_ITR",
+                mcu_name,
+                peripheral.name,
+                missing
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_peripheral_itr_info() {
+        let internal_triggers =
+            InternalTriggers::load(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/timer_itr.yaml")).unwrap();
+        let itr = |chip: &str, peripheral: &str, itr: u8| {
+            internal_triggers
+                .peripheral_itr_info(chip, peripheral)
+                .and_then(|r| r.itr.get(&itr))
+                .map(String::as_str)
+        };
+
+        // F100 connects TIM15 where the other F1 lines have TIM5 and TIM8
+        assert_eq!(itr("STM32F100RB", "TIM1", 0), Some("TIM15_TRGO"));
+        assert_eq!(itr("STM32F100RB", "TIM2", 1), Some("TIM15_TRGO"));
+        assert_eq!(itr("STM32F103RB", "TIM1", 0), Some("TIM5_TRGO"));
+        assert_eq!(itr("STM32F103RB", "TIM2", 1), Some("TIM8_TRGO"));
+
+        // H7 and L4 connect TIM15 where F4 has TIM5
+        assert_eq!(itr("STM32H743ZI", "TIM1", 0), Some("TIM15_TRGO"));
+        assert_eq!(itr("STM32L476RG", "TIM3", 2), Some("TIM15_TRGO"));
+        assert_eq!(itr("STM32H743ZI", "TIM5", 1), Some("TIM8_TRGO"));
+        assert_eq!(itr("STM32L476RG", "TIM5", 1), Some("TIM3_TRGO"));
+        // G4, H5 and U5 number the inputs by source timer and reserve the timer's own
+        assert_eq!(itr("STM32G474RE", "TIM3", 1), Some("TIM2_TRGO"));
+        assert_eq!(itr("STM32U585AI", "TIM3", 2), None);
+        assert_eq!(itr("STM32H563ZI", "TIM8", 4), Some("TIM5_TRGO"));
+        assert_eq!(itr("STM32G474RE", "TIM20", 5), Some("TIM8_TRGO"));
+        // H7R/S has no table yet
+        assert!(internal_triggers.peripheral_itr_info("STM32H7S3L8", "TIM1").is_none());
+
+        // Patterns are anchored, TIM1 must not match TIM10
+        assert!(internal_triggers.peripheral_itr_info("STM32F405RG", "TIM10").is_none());
+        assert!(internal_triggers.peripheral_itr_info("STM32F100RB", "TIM4").is_none());
+    }
+
+    fn peripheral(
+        triggers: &Triggers,
+        chip: &str,
+        name: &str,
+        version: &str,
+    ) -> stm32_data_serde::chip::core::Peripheral {
+        let rule = triggers.peripheral_trigger_info(chip, name).unwrap();
+        let triggers: Vec<_> = rule
+            .triggers
+            .iter()
+            .map(|t| {
+                serde_json::json!({
+                    "signal": t.signal,
+                    "source": t.source,
+                    "field": t.field.as_ref().map(|(field, _)| field),
+                    "value": t.field.as_ref().map(|&(_, value)| value),
+                })
+            })
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "registers": { "kind": "adc", "version": version, "block": "ADC" },
+            "triggers": triggers,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_triggers() {
+        let registers = Registers::load(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/registers")).unwrap();
+        let triggers = Triggers::load(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../data/triggers.yaml"),
+            &registers,
+        )
+        .unwrap();
+
+        let rule = triggers.peripheral_trigger_info("STM32F429ZI", "ADC1").unwrap();
+        let trg0 = rule.triggers.iter().find(|t| t.signal == "ADC_EXT_TRG0").unwrap();
+        assert_eq!(trg0.source, "TIM1_CC1");
+        assert_eq!(trg0.field, Some(("EXTSEL".to_string(), 0)));
+        assert!(triggers.peripheral_trigger_info("STM32F429ZI", "USART1").is_none());
+        // Families without an ADC table have no rule
+        assert!(triggers.peripheral_trigger_info("STM32F303RE", "ADC1").is_none());
+
+        // The tables fit the register version of the ADCs they match
+        let f4 = peripheral(&triggers, "STM32F429ZI", "ADC1", "v2");
+        assert!(triggers.check("STM32F429ZI", &f4).is_ok());
+        let mut g0 = peripheral(&triggers, "STM32G071RB", "ADC1", "g0");
+        assert!(triggers.check("STM32G071RB", &g0).is_ok());
+
+        // EXTSEL is 3 bits wide in adc_g0
+        g0.triggers[0].value = Some(8);
+        assert!(triggers.check("STM32G071RB", &g0).is_err());
+        // Unknown register version
+        g0.registers.as_mut().unwrap().version = "g9".to_string();
+        g0.triggers[0].value = Some(1);
+        assert!(triggers.check("STM32G071RB", &g0).is_err());
     }
 }
@@ -68,3 +68,98 @@ where
 {
     regex::RegexSet::new(items.into_iter().map(|k| format!("^{}$", k.as_ref()))).unwrap()
 }
+
+/// Where a rule in one of the `data/*.yaml` files comes from.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleSource {
+    /// Reference manual(s), e.g. `RM0456` or `RM0453, RM0461`, or `heuristic` for catch-all rules.
+    pub document: String,
+    /// Table of the document the rule was taken from, e.g. `Table 90` or `RM0453 Table 12, RM0461 Table 14`.
+    pub table: String,
+    /// The rule has not been checked against a numbered table yet, `table` only names it.
+    #[serde(default)]
+    pub unverified: bool,
+}
+
+/// Number of rules each data file may have with an unverified source. Verifying a rule against a
+/// numbered table lowers the count; a new unverified rule has to be allowed here explicitly.
+#[rustfmt::skip]
+const UNVERIFIED_RULES: &[(&str, usize)] = &[
+    ("data/capabilities.yaml",                4),
+    ("data/dmamux/inputs/C0_DMAMUX1.yaml",    1),
+    ("data/dmamux/inputs/G0_DMAMUX1.yaml",    1),
+    ("data/dmamux/inputs/G4_DMAMUX1.yaml",    1),
+    ("data/dmamux/inputs/H7_DMAMUX1.yaml",    1),
+    ("data/dmamux/inputs/H7_DMAMUX2.yaml",    1),
+    ("data/dmamux/inputs/L4PQ_DMAMUX1.yaml",  1),
+    ("data/dmamux/inputs/L4RS_DMAMUX1.yaml",  1),
+    ("data/dmamux/inputs/L5_DMAMUX1.yaml",    1),
+    ("data/dmamux/inputs/U0_DMAMUX1.yaml",    1),
+    ("data/dmamux/inputs/WB_DMAMUX1.yaml",    1),
+    ("data/dmamux/inputs/WL_DMAMUX1.yaml",    1),
+    ("data/exti_lines.yaml",                  25),
+    ("data/low_power.yaml",                   18),
+    ("data/rifsc/n6.yaml",                    1),
+    ("data/stop_modes.yaml",                  25),
+    ("data/timer_itr.yaml",                   29),
+    ("data/triggers.yaml",                    70),
+];
+
+impl RuleSource {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !lazy_regex::regex!(r"^((RM|AN|ES|DS)\d{4}(, (RM|AN|ES|DS)\d{4})*|heuristic)$").is_match(&self.document) {
+            anyhow::bail!("invalid source document {:?}", self.document);
+        }
+        if self.table.trim().is_empty() {
+            anyhow::bail!("source of {:?} has no table", self.document);
+        }
+        if self.document != "heuristic"
+            && !self.unverified
+            && !lazy_regex::regex!(r"^((RM|AN|ES|DS)\d{4} )?Table \d+(, ((RM|AN|ES|DS)\d{4} )?Table \d+)*$")
+                .is_match(&self.table)
+        {
+            anyhow::bail!(
+                "source table {:?} of {:?} is not a table number, cite it or mark the rule unverified",
+                self.table,
+                self.document
+            );
+        }
+        Ok(())
+    }
+
+    /// Check that the number of unverified sources of the rules of `path` is the one allowed in `UNVERIFIED_RULES`.
+    pub fn check_unverified<'a>(path: &str, sources: impl IntoIterator<Item = &'a Self>) -> anyhow::Result<()> {
+        let count = sources.into_iter().filter(|s| s.unverified).count();
+        let allowed = UNVERIFIED_RULES
+            .iter()
+            .find(|(file, _)| path.ends_with(file))
+            .map_or(0, |&(_, n)| n);
+        if count > allowed {
+            anyhow::bail!(
+                "{}: {} rules have an unverified source, only {} are allowed; cite the table numbers",
+                path,
+                count,
+                allowed
+            );
+        }
+        if count < allowed {
+            anyhow::bail!(
+                "{}: {} rules have an unverified source, lower the allowance of {} to match",
+                path,
+                count,
+                allowed
+            );
+        }
+        Ok(())
+    }
+
+    pub fn provenance(&self, data: &str) -> stm32_data_serde::chip::core::peripheral::Provenance {
+        stm32_data_serde::chip::core::peripheral::Provenance {
+            data: data.to_string(),
+            document: self.document.clone(),
+            table: self.table.clone(),
+            unverified: self.unverified,
+        }
+    }
+}
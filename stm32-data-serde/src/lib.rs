@@ -21,10 +21,101 @@ pub struct Chip {
     pub line: String,
     pub die: String,
     pub device_id: u16,
+    /// RCC version of the chip. The clock tree is in `rcc/<clock_tree>.json`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clock_tree: Option<String>,
     pub packages: Vec<chip::Package>,
     pub memory: Vec<Vec<chip::Memory>>,
+    /// Flash layout of each entry in `memory`, in the same order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flash: Vec<chip::Flash>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub boot: Option<chip::Boot>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wakeup_pins: Vec<chip::WakeupPin>,
+    /// Remaps selecting which GPIO is bonded to a package pad shared by several GPIOs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pad_remaps: Vec<chip::core::peripheral::Remap>,
     pub docs: Vec<chip::Doc>,
     pub cores: Vec<chip::Core>,
+    /// How the cores of a multi-core chip share resources. Absent on single-core chips.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multicore: Option<chip::MultiCore>,
+}
+
+/// Clock graph of an RCC version, derived from its register block.
+///
+/// Every clock has a name, and the graph is walked by name: a clock is produced by the oscillator
+/// of the same name in `sources`, by the mux, prescaler or bus clock with that `output`/`name`, or
+/// listed in the `outputs` of a PLL. A mux input may carry a fixed `_DIV_n`/`_MUL_n` suffix, e.g.
+/// `HSI_DIV_2`; strip it to get the clock.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ClockTree {
+    pub name: String,
+    /// Oscillators, e.g. `HSI`, `HSE`, `LSE`.
+    pub sources: Vec<String>,
+    pub plls: Vec<clock_tree::Pll>,
+    pub prescalers: Vec<clock_tree::Prescaler>,
+    /// Bus clocks, e.g. `HCLK1`, `PCLK1`, `PCLK1_TIM`.
+    pub bus_clocks: Vec<clock_tree::BusClock>,
+    pub muxes: Vec<clock_tree::Mux>,
+}
+
+pub mod clock_tree {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+    pub struct Pll {
+        pub name: String,
+        /// Mux selecting the PLL input clock.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub source: Option<crate::chip::core::peripheral::rcc::Field>,
+        /// Reference clock of the PLL, e.g. `PLL2_REF` behind its own `PLLM` prescaler, `PLL_REF`
+        /// behind one shared by all PLLs, or the output of the source mux when there is no input
+        /// divider.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub input: Option<String>,
+        /// Output clocks, e.g. `PLL1_P`, `PLL1_Q`. Outputs with a post-divider are also the output
+        /// of a prescaler fed from the VCO clock `<name>_VCO`.
+        pub outputs: Vec<String>,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+    pub struct Prescaler {
+        pub register: String,
+        pub field: String,
+        /// Element of the register or field array, e.g. `1` for the second PLL of `PLLCFGR`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub index: Option<u32>,
+        /// Clock being divided, e.g. `HCLK` for `PPRE1`.
+        pub input: String,
+        /// Divided clock, e.g. `PCLK1` for `PPRE1`.
+        pub output: String,
+        pub divisors: Vec<u32>,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+    pub struct Mux {
+        pub register: String,
+        pub field: String,
+        /// Element of the register or field array, e.g. `1` for the second PLL of `PLLCFGR`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub index: Option<u32>,
+        /// Selected clock, e.g. `SYS` for `SW`, `USART1` for `USART1SEL`, `PLL_IN` for a PLL source
+        /// mux shared by all PLLs or `PLL2_IN` for one that only feeds `PLL2`.
+        pub output: String,
+        pub inputs: Vec<String>,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+    pub struct BusClock {
+        pub name: String,
+        /// Clock of the graph that drives the bus, e.g. `HCLK` for `HCLK2` or `PCLK1` for
+        /// `PCLK1_TIM`. Timers run at twice that clock when the APB prescaler divides. Absent when
+        /// the RCC version has no prescaler for it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub clock: Option<String>,
+    }
 }
 
 pub mod chip {
@@ -48,12 +139,129 @@ pub mod chip {
         pub name: String,
         pub kind: memory::Kind,
         pub address: u32,
+        /// Secure alias of the memory address, on TrustZone chips.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub secure_address: Option<u32>,
         pub size: u32,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub settings: Option<memory::Settings>,
         // probe-rs needs access attributes for automated tests
         #[serde(skip_serializing_if = "Option::is_none")]
         pub access: Option<memory::Access>,
+        #[serde(default, skip_serializing_if = "crate::is_default")]
+        pub attributes: memory::Attributes,
+        /// Peripheral that provides an external memory window, e.g. `FMC` or `OCTOSPI1`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub peripheral: Option<String>,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+    pub struct Flash {
+        pub banks: Vec<flash::Bank>,
+        /// Programming granularity in bytes.
+        pub write_size: u32,
+        pub erase_value: u8,
+        /// Option bit selecting this layout, on chips where the bank organization is configurable.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub bank_mode: Option<flash::BankMode>,
+        /// Option bit swapping the bank mapping at boot.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub bank_swap: Option<flash::OptionBit>,
+        /// One bank can be read while the other is being programmed or erased. Absent if not known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub read_while_write: Option<bool>,
+    }
+
+    pub mod flash {
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        pub struct Bank {
+            /// Name of the matching flash memory region, e.g. `BANK_1`.
+            pub name: String,
+            pub address: u32,
+            pub size: u32,
+            pub sectors: Vec<Sector>,
+        }
+
+        /// Smallest erasable unit, called sector or page depending on the family.
+        #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        pub struct Sector {
+            /// Sector number as written to the flash controller's erase register.
+            pub index: u32,
+            /// Offset from the start of the bank.
+            pub offset: u32,
+            pub size: u32,
+        }
+
+        #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        pub struct OptionBit {
+            pub register: String,
+            pub field: String,
+        }
+
+        #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        pub struct BankMode {
+            pub register: String,
+            pub field: String,
+            /// Value of the option bit for this layout.
+            pub value: u8,
+        }
+    }
+
+    #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+    pub struct Boot {
+        /// Pin sampled at reset to select the boot memory: `BOOT0` for a dedicated pin, or the GPIO
+        /// sharing its pad, e.g. `PH3`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub boot0: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub boot1: Option<String>,
+        /// Option bits taking part in the boot selection, e.g. `nBOOT0` or `nSWBOOT0`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub option_bits: Vec<flash::OptionBit>,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+    pub struct WakeupPin {
+        /// Wakeup line number, as in `WKUP1`.
+        pub line: u8,
+        /// Pins that can drive the line.
+        pub pins: Vec<String>,
+        pub enable: wakeup_pin::Field,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub polarity: Option<wakeup_pin::Field>,
+        /// Deepest low-power mode the pin can wake the chip from.
+        pub exits: LowPowerMode,
+    }
+
+    pub mod wakeup_pin {
+        use serde::{Deserialize, Serialize};
+
+        /// Field of a PWR register.
+        #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        pub struct Field {
+            pub register: String,
+            pub field: String,
+            /// Index into the field array, e.g. `EWUP[2]` for line 3.
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            pub index: Option<u32>,
+        }
+    }
+
+    /// Low-power modes, from shallowest to deepest.
+    #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum LowPowerMode {
+        Sleep,
+        /// Stop mode of families that have a single one.
+        Stop,
+        Stop0,
+        Stop1,
+        Stop2,
+        Stop3,
+        Standby,
+        Shutdown,
     }
 
     pub mod memory {
@@ -65,6 +273,18 @@ pub mod chip {
             Flash,
             Ram,
             Eeprom,
+            /// Memory-mapped window of an external memory controller. Only backed by memory if
+            /// a device is connected and the controller is configured.
+            External,
+            /// One-time programmable area of the flash. The `OTP` regions of the memory tables
+            /// used to be listed as `flash`; code looking for them by kind needs to match this one.
+            Otp,
+            /// Option bytes, loaded into the flash option registers at reset.
+            #[serde(rename = "option_bytes")]
+            OptionBytes,
+            /// System memory holding the ST bootloader.
+            #[serde(rename = "system_memory")]
+            SystemMemory,
         }
 
         #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
@@ -80,6 +300,85 @@ pub mod chip {
             pub write: bool,
             pub execute: bool,
         }
+
+        #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        pub struct Attributes {
+            /// Hardware error detection on the memory contents. Absent when not known, `none` when the
+            /// memory is known to have no error detection.
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            pub protection: Option<Protection>,
+            /// Deepest low-power mode in which the contents are kept. Absent when not known.
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            pub retention: Option<Retention>,
+            /// DMA controllers, by peripheral name, with no bus path to the memory, e.g. `DMA1` for the
+            /// H7 DTCM. The CPU cores reach every memory. This only covers the bus matrix, whatever the
+            /// security state of the master: GTZC/RIF filtering depends on the runtime configuration and
+            /// is not listed.
+            #[serde(default, skip_serializing_if = "Vec::is_empty")]
+            pub inaccessible_by: Vec<String>,
+            /// Other addresses the same memory is mapped at, e.g. SRAM2 on the ICODE bus on L4.
+            /// Secure aliases are in `secure_address`.
+            #[serde(default, skip_serializing_if = "Vec::is_empty")]
+            pub aliases: Vec<u32>,
+        }
+
+        #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(rename_all = "lowercase")]
+        pub enum Protection {
+            Ecc,
+            Parity,
+            /// No error detection
+            None,
+        }
+
+        #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(rename_all = "lowercase")]
+        pub enum Retention {
+            Stop,
+            Standby,
+            /// Kept in VBAT mode
+            Backup,
+        }
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+    pub struct MultiCore {
+        /// Peripherals every core can access.
+        pub shared_peripherals: Vec<String>,
+        pub cores: Vec<multicore::CoreView>,
+        /// Peripherals the cores use to synchronize and signal each other, e.g. `HSEM` and `IPCC`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub mailboxes: Vec<multicore::Mailbox>,
+    }
+
+    pub mod multicore {
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        pub struct CoreView {
+            pub core: String,
+            /// Peripherals only this core can access.
+            #[serde(default, skip_serializing_if = "Vec::is_empty")]
+            pub private_peripherals: Vec<String>,
+            /// Interrupt vectors only this core has, e.g. `HSEM2` on the H7 CM4.
+            #[serde(default, skip_serializing_if = "Vec::is_empty")]
+            pub private_interrupts: Vec<String>,
+            /// Memory regions this core can reach, at the address it sees them.
+            pub memory: Vec<MemoryView>,
+        }
+
+        #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        pub struct MemoryView {
+            pub name: String,
+            pub address: u32,
+        }
+
+        #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        pub struct Mailbox {
+            pub peripheral: String,
+            /// Number of semaphores for `HSEM`, of channels per direction for `IPCC`.
+            pub channels: u8,
+        }
     }
 
     #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
@@ -97,6 +396,9 @@ pub mod chip {
         #[serde(skip_serializing_if = "Option::is_none")]
         pub nvic_priority_bits: Option<u8>,
         pub interrupts: Vec<core::Interrupt>,
+        /// Full vector table of the core, including system exceptions.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub vector_table: Option<core::VectorTable>,
         pub dma_channels: Vec<core::DmaChannels>,
         pub pins: Vec<core::Pin>,
     }
@@ -107,22 +409,54 @@ pub mod chip {
         #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
         pub struct Peripheral {
             pub name: String,
+            /// Peripheral base address. On TrustZone chips this is the non-secure alias.
             #[serde(default)]
             pub address: u32,
+            /// Secure alias of the peripheral base address, on TrustZone chips.
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            pub secure_address: Option<u32>,
+            /// Size of the address range in bytes, from the register block or the spacing to the next peripheral.
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            pub size: Option<u32>,
+            /// `size` is the spacing to the next peripheral, not the extent of the register block.
+            #[serde(default, skip_serializing_if = "crate::is_default")]
+            pub size_inferred: bool,
             #[serde(default, skip_serializing_if = "Option::is_none")]
             pub registers: Option<peripheral::Registers>,
             #[serde(default, skip_serializing_if = "Option::is_none")]
             pub rcc: Option<peripheral::Rcc>,
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            pub debug_freeze: Option<peripheral::DebugFreeze>,
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            pub security: Option<peripheral::Security>,
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            pub capabilities: Option<peripheral::Capabilities>,
             #[serde(default, skip_serializing_if = "Vec::is_empty")]
             pub pins: Vec<peripheral::Pin>,
             #[serde(default, skip_serializing_if = "Vec::is_empty")]
             pub interrupts: Vec<peripheral::Interrupt>,
             #[serde(default, skip_serializing_if = "Vec::is_empty")]
             pub dma_channels: Vec<peripheral::DmaChannel>,
+            /// DMAMUX synchronization and trigger inputs driven by this peripheral.
+            #[serde(default, skip_serializing_if = "Vec::is_empty")]
+            pub dmamux_inputs: Vec<peripheral::DmamuxInput>,
+            /// GPDMA/HPDMA/LPDMA hardware triggers driven by this peripheral.
+            #[serde(default, skip_serializing_if = "Vec::is_empty")]
+            pub dma_triggers: Vec<peripheral::DmaTrigger>,
             #[serde(default, skip_serializing_if = "Vec::is_empty")]
             pub triggers: Vec<peripheral::Trigger>,
+            /// Timer internal trigger (`ITRx`) inputs and the signals connected to them.
+            #[serde(default, skip_serializing_if = "Vec::is_empty")]
+            pub internal_triggers: Vec<peripheral::InternalTrigger>,
+            /// Pin remaps through AFIO or SYSCFG that change which pins the peripheral's signals are on.
+            #[serde(default, skip_serializing_if = "Vec::is_empty")]
+            pub remaps: Vec<peripheral::Remap>,
+            /// Deprecated, the first `AFIO` entry of `remaps`. Kept for existing consumers.
             #[serde(default, skip_serializing_if = "Option::is_none")]
             pub afio: Option<peripheral::Afio>,
+            /// Where the hand-maintained data in `data/` that applied to this peripheral comes from.
+            #[serde(default, skip_serializing_if = "Vec::is_empty")]
+            pub provenance: Vec<peripheral::Provenance>,
         }
 
         pub mod peripheral {
@@ -142,8 +476,103 @@ pub mod chip {
                 pub enable: rcc::Field,
                 #[serde(skip_serializing_if = "Option::is_none")]
                 pub reset: Option<rcc::Field>,
+                /// Clock enable bit during Sleep mode (`xxLPENR` or `xxSMENR`).
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub low_power_enable: Option<rcc::Field>,
+                /// Clock enable bit during autonomous mode (`xxAMENR` or `SRDAMR`).
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub autonomous_enable: Option<rcc::Field>,
                 #[serde(default, skip_serializing_if = "crate::is_default")]
                 pub stop_mode: rcc::StopMode,
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub low_power: Option<rcc::LowPower>,
+            }
+
+            /// DBGMCU bit that stops the peripheral while the core is halted by the debugger.
+            #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+            pub struct DebugFreeze {
+                pub register: String,
+                pub field: String,
+            }
+
+            /// TrustZone / RIF attribution of the peripheral.
+            #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+            pub struct Security {
+                /// GTZC TZSC secure configuration bit (`SECCFGRx`).
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub secure: Option<security::Field>,
+                /// GTZC TZSC privilege configuration bit (`PRIVCFGRx`).
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub privilege: Option<security::Field>,
+                /// RIFSC RISC index, selecting the bit in the `RISC_SECCFGRx`/`RISC_PRIVCFGRx` arrays.
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub risc_index: Option<u32>,
+                /// RIFSC RIMC indices of the bus masters of the peripheral, selecting the `RIMC_ATTRx`
+                /// registers that set their CID and security attributes.
+                #[serde(default, skip_serializing_if = "Vec::is_empty")]
+                pub rimc_indices: Vec<u32>,
+            }
+
+            pub mod security {
+                use serde::{Deserialize, Serialize};
+
+                #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+                pub struct Field {
+                    pub register: String,
+                    pub field: String,
+                }
+            }
+
+            /// What the instance can do, derived from its register block by the rules in
+            /// `data/capabilities.yaml`.
+            #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+            pub struct Capabilities {
+                /// Counter width in bits, for timers.
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub counter_bits: Option<u8>,
+                /// Number of capture/compare channels, for timers.
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub channels: Option<u8>,
+                /// Number of capture/compare channels with a complementary output, for timers.
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub complementary_channels: Option<u8>,
+                /// Depth of the data FIFOs in bytes.
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub fifo_depth: Option<u8>,
+                /// Selectable conversion resolutions in bits, highest first, for ADCs.
+                #[serde(default, skip_serializing_if = "Vec::is_empty")]
+                pub resolutions: Vec<u8>,
+                #[serde(default, skip_serializing_if = "Vec::is_empty")]
+                pub features: Vec<capabilities::Feature>,
+            }
+
+            pub mod capabilities {
+                use serde::{Deserialize, Serialize};
+
+                #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+                #[serde(rename_all = "snake_case")]
+                pub enum Feature {
+                    /// Transmit and receive FIFOs, for U(S)ARTs.
+                    Fifo,
+                    Lin,
+                    Smartcard,
+                    Irda,
+                    AutoBaudRate,
+                    /// Driver enable output for RS-485 transceivers.
+                    DriverEnable,
+                    /// I2S mode, for SPIs.
+                    I2s,
+                    /// Hardware CRC calculation, for SPIs.
+                    Crc,
+                    /// Break input, for timers.
+                    Break,
+                    /// PWM dithering, for timers.
+                    Dithering,
+                    /// Hardware oversampling, for ADCs.
+                    Oversampling,
+                    /// Differential inputs, for ADCs.
+                    Differential,
+                }
             }
 
             pub mod rcc {
@@ -159,7 +588,41 @@ pub mod chip {
                 #[serde(untagged)]
                 pub enum KernelClock {
                     Clock(String),
-                    Mux(Field),
+                    Mux(Mux),
+                }
+
+                #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+                pub struct Mux {
+                    pub register: String,
+                    pub field: String,
+                    /// Selectable clock sources, in field value order.
+                    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+                    pub sources: Vec<MuxSource>,
+                }
+
+                #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+                pub struct MuxSource {
+                    /// Clock name, e.g. `LSE` or `PLL1_Q`.
+                    pub name: String,
+                    /// Value to write to the mux field to select this source.
+                    pub value: u32,
+                }
+
+                /// Behavior of the peripheral in low-power modes.
+                #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+                pub struct LowPower {
+                    /// Deepest mode in which the peripheral keeps working.
+                    #[serde(default, skip_serializing_if = "Option::is_none")]
+                    pub functional_in: Option<crate::chip::LowPowerMode>,
+                    /// The peripheral can wake the chip up from `functional_in`.
+                    #[serde(default, skip_serializing_if = "crate::is_default")]
+                    pub wakeup: bool,
+                    /// Registers keep their contents in Standby.
+                    #[serde(default, skip_serializing_if = "crate::is_default")]
+                    pub retention: bool,
+                    /// Powered from VBAT and only reset by a backup domain reset.
+                    #[serde(default, skip_serializing_if = "crate::is_default")]
+                    pub backup_domain: bool,
                 }
 
                 #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize, Default)]
@@ -188,12 +651,51 @@ pub mod chip {
             pub struct Interrupt {
                 pub signal: String,
                 pub interrupt: String,
+                /// EXTI lines raising the signal: the lines served by an EXTI vector, or the lines of a
+                /// peripheral signal such as PVD or RTC wakeup.
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub exti_lines: Option<ExtiLines>,
+            }
+
+            /// Inclusive range of EXTI lines, e.g. 10 to 15 for `EXTI15_10`.
+            #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+            pub struct ExtiLines {
+                pub first: u8,
+                pub last: u8,
             }
 
             #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
             pub struct Trigger {
                 pub signal: String,
                 pub source: String,
+                /// Register field selecting this trigger, e.g. `EXTSEL`.
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub field: Option<String>,
+                /// Value of `field` selecting this trigger.
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub value: Option<u8>,
+            }
+
+            #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+            pub struct InternalTrigger {
+                /// Number of the `ITRx` input.
+                pub itr: u8,
+                /// Signal connected to it, e.g. `TIM2_TRGO`.
+                pub source: String,
+            }
+
+            /// Source of a rule from a data file in `data/`.
+            #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+            pub struct Provenance {
+                /// The data the rule provided, e.g. `stop_mode` or `triggers`.
+                pub data: String,
+                /// Reference manual(s) the rule was taken from, e.g. `RM0456`, or `heuristic`.
+                pub document: String,
+                /// Table of the document, e.g. `Table 90`, or its title if the rule is unverified.
+                pub table: String,
+                /// The rule has not been checked against a numbered table of the document yet.
+                #[serde(default, skip_serializing_if = "crate::is_default")]
+                pub unverified: bool,
             }
 
             #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
@@ -211,6 +713,26 @@ pub mod chip {
                 pub request: Option<u8>,
             }
 
+            #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+            pub struct DmamuxInput {
+                pub dmamux: String,
+                pub signal: String,
+                /// Value of `DMAMUX_CxCR.SYNC_ID` selecting this signal as synchronization input.
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub sync_id: Option<u8>,
+                /// Value of `DMAMUX_RGxCR.SIG_ID` selecting this signal as request generator trigger.
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub trigger_id: Option<u8>,
+            }
+
+            #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+            pub struct DmaTrigger {
+                pub dma: String,
+                pub signal: String,
+                /// Value of `DMA_CxTR2.TRIGSEL` selecting this signal as hardware trigger.
+                pub trigger: u8,
+            }
+
             #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
             pub struct RemapInfo {
                 pub register: String,
@@ -218,6 +740,23 @@ pub mod chip {
                 pub value: u8,
             }
 
+            #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+            pub struct Remap {
+                /// Peripheral holding the remap register, e.g. `AFIO` or `SYSCFG`.
+                pub peripheral: String,
+                pub register: String,
+                pub field: String,
+                #[serde(skip_serializing_if = "Vec::is_empty")]
+                pub values: Vec<RemapValue>,
+            }
+
+            #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+            pub struct RemapValue {
+                pub value: u8,
+                #[serde(default, skip_serializing_if = "Vec::is_empty")]
+                pub pins: Vec<String>,
+            }
+
             #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
             pub struct Afio {
                 pub register: String,
@@ -240,6 +779,27 @@ pub mod chip {
             pub number: u8,
         }
 
+        #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        pub struct VectorTable {
+            /// Number of entries, including the initial stack pointer and the system exceptions.
+            pub entries: u16,
+            /// Required alignment of the table in bytes for relocating it with `VTOR`. `None` for cores
+            /// without `VTOR` (Cortex-M0), whose table stays at address 0.
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            pub alignment: Option<u32>,
+            /// System exceptions implemented by the core.
+            pub exceptions: Vec<Exception>,
+            /// Exception numbers of the entries without a handler, both system and device interrupt slots.
+            pub reserved: Vec<u16>,
+        }
+
+        #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        pub struct Exception {
+            pub name: String,
+            /// Exception number, which is the index into the vector table.
+            pub number: u8,
+        }
+
         #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
         pub struct Pin {
             pub name: String,
@@ -48,7 +48,30 @@ fn main() {
         chip_core_name,
     );
 
-    println!("cargo:rustc-env=STM32_METAPAC_PAC_PATH=chips/{}/pac.rs", chip_core_name);
+    // With the `secure` feature, use the PAC with peripherals at their TrustZone secure alias.
+    // Chips without TrustZone have none, lib.rs then fails the build with a `compile_error!`.
+    println!("cargo:rustc-check-cfg=cfg(stm32_metapac_no_trustzone)");
+    let pac_file = if env::var_os("CARGO_FEATURE_SECURE").is_some() {
+        let crate_dir = std::path::PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+        if crate_dir
+            .join("src/chips")
+            .join(&chip_core_name)
+            .join("pac_secure.rs")
+            .exists()
+        {
+            "pac_secure.rs"
+        } else {
+            println!("cargo:rustc-cfg=stm32_metapac_no_trustzone");
+            "pac.rs"
+        }
+    } else {
+        "pac.rs"
+    };
+
+    println!(
+        "cargo:rustc-env=STM32_METAPAC_PAC_PATH=chips/{}/{}",
+        chip_core_name, pac_file
+    );
     println!(
         "cargo:rustc-env=STM32_METAPAC_METADATA_PATH=chips/{}/metadata.rs",
         chip_core_name
@@ -3,6 +3,7 @@
 #![allow(unused)]
 #![allow(non_camel_case_types)]
 #![allow(non_upper_case_globals)]
+#![allow(deprecated)]
 #![doc(html_no_source)]
 #![cfg_attr(
     docsrs,
@@ -12,6 +13,9 @@
 
 pub mod common;
 
+#[cfg(stm32_metapac_no_trustzone)]
+compile_error!("The `secure` feature is enabled, but the selected chip has no TrustZone");
+
 #[cfg(feature = "pac")]
 include!(env!("STM32_METAPAC_PAC_PATH"));
 
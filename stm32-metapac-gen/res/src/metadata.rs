@@ -130,6 +130,7 @@ pub struct Metadata {
     pub peripherals: &'static [Peripheral],
     pub nvic_priority_bits: Option<u8>,
     pub interrupts: &'static [Interrupt],
+    pub vector_table: Option<VectorTable>,
     pub dma_channels: &'static [DmaChannel],
     pub pins: &'static [Pin],
 }
@@ -139,8 +140,39 @@ pub struct MemoryRegion {
     pub name: &'static str,
     pub kind: MemoryRegionKind,
     pub address: u32,
+    pub secure_address: Option<u32>,
     pub size: u32,
     pub settings: Option<FlashSettings>,
+    pub attributes: MemoryAttributes,
+    /// Peripheral that provides an external memory window.
+    pub peripheral: Option<&'static str>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct MemoryAttributes {
+    /// Hardware error detection on the memory contents, `None` when not known.
+    pub protection: Option<MemoryProtection>,
+    /// Deepest low-power mode in which the contents are kept, `None` when not known.
+    pub retention: Option<MemoryRetention>,
+    /// DMA controllers with no bus path to the memory, whatever their security state.
+    pub inaccessible_by: &'static [&'static str],
+    /// Other addresses the same memory is mapped at.
+    pub aliases: &'static [u32],
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum MemoryProtection {
+    Ecc,
+    Parity,
+    None,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum MemoryRetention {
+    Stop,
+    Standby,
+    /// Kept in VBAT mode
+    Backup,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -155,6 +187,13 @@ pub enum MemoryRegionKind {
     Flash,
     Ram,
     Eeprom,
+    /// Memory-mapped window of an external memory controller.
+    External,
+    /// One-time programmable area of the flash. Listed as `Flash` in earlier releases.
+    Otp,
+    OptionBytes,
+    /// ST bootloader
+    SystemMemory,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -163,6 +202,20 @@ pub struct Interrupt {
     pub number: u32,
 }
 
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct VectorTable {
+    pub entries: u16,
+    pub alignment: Option<u32>,
+    pub exceptions: &'static [Exception],
+    pub reserved: &'static [u16],
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Exception {
+    pub name: &'static str,
+    pub number: u8,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct Package {
     pub name: &'static str,
@@ -173,13 +226,26 @@ pub struct Package {
 pub struct Peripheral {
     pub name: &'static str,
     pub address: u64,
+    pub secure_address: Option<u64>,
+    pub size: Option<u32>,
+    /// `size` is the spacing to the next peripheral, not the extent of the register block.
+    pub size_inferred: bool,
     pub registers: Option<PeripheralRegisters>,
     pub rcc: Option<PeripheralRcc>,
+    pub debug_freeze: Option<PeripheralDebugFreeze>,
+    pub security: Option<PeripheralSecurity>,
+    pub capabilities: Option<PeripheralCapabilities>,
     pub pins: &'static [PeripheralPin],
     pub dma_channels: &'static [PeripheralDmaChannel],
+    pub dmamux_inputs: &'static [PeripheralDmamuxInput],
+    pub dma_triggers: &'static [PeripheralDmaTrigger],
     pub triggers: &'static [PeripheralTrigger],
+    pub internal_triggers: &'static [PeripheralInternalTrigger],
     pub interrupts: &'static [PeripheralInterrupt],
+    /// AFIO remap of the peripheral, the first AFIO entry of `remaps`.
+    #[deprecated(note = "use `remaps`, which also has the SYSCFG remaps and every remap field of the peripheral")]
     pub afio: Option<PeripheralAfio>,
+    pub remaps: &'static [PeripheralRemap],
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -194,6 +260,27 @@ pub struct PeripheralRegisters {
 pub struct PeripheralInterrupt {
     pub signal: &'static str,
     pub interrupt: &'static str,
+    pub exti_lines: Option<PeripheralExtiLines>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PeripheralExtiLines {
+    pub first: u8,
+    pub last: u8,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PeripheralRemap {
+    pub peripheral: &'static str,
+    pub register: &'static str,
+    pub field: &'static str,
+    pub values: &'static [PeripheralRemapValue],
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PeripheralRemapValue {
+    pub value: u8,
+    pub pins: &'static [&'static str],
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -215,7 +302,88 @@ pub struct PeripheralRcc {
     pub kernel_clock: PeripheralRccKernelClock,
     pub enable: Option<PeripheralRccRegister>,
     pub reset: Option<PeripheralRccRegister>,
+    pub low_power_enable: Option<PeripheralRccRegister>,
+    pub autonomous_enable: Option<PeripheralRccRegister>,
     pub stop_mode: StopMode,
+    pub low_power: Option<PeripheralRccLowPower>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PeripheralRccLowPower {
+    /// Deepest mode in which the peripheral keeps working.
+    pub functional_in: Option<LowPowerMode>,
+    /// The peripheral can wake the chip up from `functional_in`.
+    pub wakeup: bool,
+    /// Registers keep their contents in Standby.
+    pub retention: bool,
+    /// Powered from VBAT and only reset by a backup domain reset.
+    pub backup_domain: bool,
+}
+
+/// Low-power modes, from shallowest to deepest.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, PartialOrd, Ord)]
+pub enum LowPowerMode {
+    Sleep,
+    /// Stop mode of families that have a single one.
+    Stop,
+    Stop0,
+    Stop1,
+    Stop2,
+    Stop3,
+    Standby,
+    Shutdown,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PeripheralDebugFreeze {
+    pub register: &'static str,
+    pub field: &'static str,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PeripheralSecurity {
+    pub secure: Option<PeripheralSecurityRegister>,
+    pub privilege: Option<PeripheralSecurityRegister>,
+    pub risc_index: Option<u32>,
+    pub rimc_indices: &'static [u32],
+}
+
+/// What the peripheral instance can do, derived from its register block.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PeripheralCapabilities {
+    /// Counter width in bits, for timers.
+    pub counter_bits: Option<u8>,
+    /// Number of capture/compare channels, for timers.
+    pub channels: Option<u8>,
+    /// Number of capture/compare channels with a complementary output, for timers.
+    pub complementary_channels: Option<u8>,
+    /// Depth of the data FIFOs in bytes.
+    pub fifo_depth: Option<u8>,
+    /// Selectable conversion resolutions in bits, highest first, for ADCs.
+    pub resolutions: &'static [u8],
+    pub features: &'static [PeripheralFeature],
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum PeripheralFeature {
+    Fifo,
+    Lin,
+    Smartcard,
+    Irda,
+    AutoBaudRate,
+    DriverEnable,
+    I2s,
+    Crc,
+    Break,
+    Dithering,
+    Oversampling,
+    Differential,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PeripheralSecurityRegister {
+    pub register: &'static str,
+    pub field: &'static str,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -227,7 +395,21 @@ pub struct PeripheralRccRegister {
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum PeripheralRccKernelClock {
     Clock(&'static str),
-    Mux(PeripheralRccRegister),
+    Mux(PeripheralRccMux),
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PeripheralRccMux {
+    pub register: &'static str,
+    pub field: &'static str,
+    /// Selectable clock sources, in field value order.
+    pub sources: &'static [PeripheralRccMuxSource],
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PeripheralRccMuxSource {
+    pub name: &'static str,
+    pub value: u32,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Default)]
@@ -270,10 +452,33 @@ pub struct PeripheralDmaChannel {
     pub request: Option<u32>,
 }
 
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PeripheralDmamuxInput {
+    pub dmamux: &'static str,
+    pub signal: &'static str,
+    pub sync_id: Option<u8>,
+    pub trigger_id: Option<u8>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PeripheralDmaTrigger {
+    pub dma: &'static str,
+    pub signal: &'static str,
+    pub trigger: u8,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct PeripheralTrigger {
     pub signal: &'static str,
     pub source: &'static str,
+    pub field: Option<&'static str>,
+    pub value: Option<u8>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PeripheralInternalTrigger {
+    pub itr: u8,
+    pub source: &'static str,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -281,6 +281,15 @@ pub mod ir {
     }
 }
 
+/// Formats an integer as hex in the generated metadata, e.g. inside an `Option`.
+struct Hex<T>(T);
+
+impl<T: std::fmt::LowerHex> std::fmt::Debug for Hex<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:#x}", self.0)
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
 pub struct Chip {
     pub name: String,
@@ -299,8 +308,14 @@ pub struct MemoryRegion {
     pub name: String,
     pub kind: MemoryRegionKind,
     pub address: u32,
+    #[serde(default)]
+    pub secure_address: Option<u32>,
     pub size: u32,
     pub settings: Option<FlashSettings>,
+    #[serde(default)]
+    pub attributes: MemoryAttributes,
+    #[serde(default)]
+    pub peripheral: Option<String>,
 }
 
 // Notice:
@@ -311,12 +326,60 @@ impl std::fmt::Debug for MemoryRegion {
             .field("name", &self.name)
             .field("kind", &self.kind)
             .field("address", &format_args!("{:#x}", self.address))
+            .field("secure_address", &self.secure_address.map(Hex))
             .field("size", &self.size)
             .field("settings", &self.settings)
+            .field("attributes", &self.attributes)
+            .field("peripheral", &self.peripheral)
             .finish()
     }
 }
 
+#[derive(Eq, PartialEq, Clone, Deserialize, Default)]
+pub struct MemoryAttributes {
+    #[serde(default)]
+    pub protection: Option<MemoryProtection>,
+    #[serde(default)]
+    pub retention: Option<MemoryRetention>,
+    #[serde(default)]
+    pub inaccessible_by: Vec<String>,
+    #[serde(default)]
+    pub aliases: Vec<u32>,
+}
+
+// Notice:
+// Debug implement AFFECT OUTPUT METAPAC, modify with caution
+impl std::fmt::Debug for MemoryAttributes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryAttributes")
+            .field("protection", &self.protection)
+            .field("retention", &self.retention)
+            .field("inaccessible_by", &self.inaccessible_by)
+            .field("aliases", &self.aliases.iter().copied().map(Hex).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[derive(EnumDebug, Eq, PartialEq, Clone, Deserialize)]
+pub enum MemoryProtection {
+    #[serde(rename = "ecc")]
+    Ecc,
+    #[serde(rename = "parity")]
+    Parity,
+    #[serde(rename = "none")]
+    None,
+}
+
+#[derive(EnumDebug, Eq, PartialEq, Clone, Deserialize)]
+pub enum MemoryRetention {
+    #[serde(rename = "stop")]
+    Stop,
+    #[serde(rename = "standby")]
+    Standby,
+    #[serde(rename = "backup")]
+    Backup,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
 pub struct FlashSettings {
     pub erase_size: u32,
@@ -332,6 +395,14 @@ pub enum MemoryRegionKind {
     Ram,
     #[serde(rename = "eeprom")]
     Eeprom,
+    #[serde(rename = "external")]
+    External,
+    #[serde(rename = "otp")]
+    Otp,
+    #[serde(rename = "option_bytes")]
+    OptionBytes,
+    #[serde(rename = "system_memory")]
+    SystemMemory,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
@@ -341,6 +412,8 @@ pub struct Core {
     #[serde(default)]
     pub nvic_priority_bits: Option<u8>,
     pub interrupts: Vec<Interrupt>,
+    #[serde(default)]
+    pub vector_table: Option<VectorTable>,
     pub dma_channels: Vec<DmaChannel>,
     pub pins: Vec<Pin>,
 }
@@ -351,6 +424,20 @@ pub struct Interrupt {
     pub number: u32,
 }
 
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+pub struct VectorTable {
+    pub entries: u16,
+    pub alignment: Option<u32>,
+    pub exceptions: Vec<Exception>,
+    pub reserved: Vec<u16>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+pub struct Exception {
+    pub name: String,
+    pub number: u8,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
 pub struct Package {
     pub name: String,
@@ -365,19 +452,37 @@ pub struct Peripheral {
     pub name: String,
     pub address: u64,
     #[serde(default)]
+    pub secure_address: Option<u64>,
+    #[serde(default)]
+    pub size: Option<u32>,
+    #[serde(default)]
+    pub size_inferred: bool,
+    #[serde(default)]
     pub registers: Option<PeripheralRegisters>,
     #[serde(default)]
     pub rcc: Option<PeripheralRcc>,
     #[serde(default)]
+    pub debug_freeze: Option<PeripheralDebugFreeze>,
+    #[serde(default)]
+    pub security: Option<PeripheralSecurity>,
+    #[serde(default)]
+    pub capabilities: Option<PeripheralCapabilities>,
+    #[serde(default)]
     pub pins: Vec<PeripheralPin>,
     #[serde(default)]
     pub dma_channels: Vec<PeripheralDmaChannel>,
     #[serde(default)]
+    pub dmamux_inputs: Vec<PeripheralDmamuxInput>,
+    #[serde(default)]
+    pub dma_triggers: Vec<PeripheralDmaTrigger>,
+    #[serde(default)]
     pub triggers: Vec<PeripheralTrigger>,
     #[serde(default)]
+    pub internal_triggers: Vec<PeripheralInternalTrigger>,
+    #[serde(default)]
     pub interrupts: Vec<PeripheralInterrupt>,
     #[serde(default)]
-    pub afio: Option<PeripheralAfio>,
+    pub remaps: Vec<PeripheralRemap>,
 }
 
 // Notice:
@@ -387,38 +492,156 @@ impl std::fmt::Debug for Peripheral {
         f.debug_struct("Peripheral")
             .field("name", &self.name)
             .field("address", &format_args!("{:#x}", self.address))
+            .field("secure_address", &self.secure_address.map(Hex))
+            .field("size", &self.size.map(Hex))
+            .field("size_inferred", &self.size_inferred)
             .field("registers", &self.registers)
             .field("rcc", &self.rcc)
+            .field("debug_freeze", &self.debug_freeze)
+            .field("security", &self.security)
+            .field("capabilities", &self.capabilities)
             .field("pins", &self.pins)
             .field("dma_channels", &self.dma_channels)
+            .field("dmamux_inputs", &self.dmamux_inputs)
+            .field("dma_triggers", &self.dma_triggers)
             .field("triggers", &self.triggers)
+            .field("internal_triggers", &self.internal_triggers)
             .field("interrupts", &self.interrupts)
-            .field("afio", &self.afio)
+            .field("afio", &self.afio())
+            .field("remaps", &self.remaps)
             .finish()
     }
 }
 
+impl Peripheral {
+    /// The first AFIO remap, for the deprecated `afio` field of the metadata.
+    fn afio(&self) -> Option<PeripheralAfio> {
+        let remap = self.remaps.iter().find(|r| r.peripheral == "AFIO")?;
+        Some(PeripheralAfio {
+            register: remap.register.clone(),
+            field: remap.field.clone(),
+            values: remap
+                .values
+                .iter()
+                .map(|v| PeripheralAfioValue {
+                    value: v.value,
+                    pins: v.pins.clone(),
+                })
+                .collect(),
+        })
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+pub struct PeripheralDebugFreeze {
+    pub register: String,
+    pub field: String,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+pub struct PeripheralSecurity {
+    #[serde(default)]
+    pub secure: Option<PeripheralSecurityRegister>,
+    #[serde(default)]
+    pub privilege: Option<PeripheralSecurityRegister>,
+    #[serde(default)]
+    pub risc_index: Option<u32>,
+    #[serde(default)]
+    pub rimc_indices: Vec<u32>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+pub struct PeripheralCapabilities {
+    #[serde(default)]
+    pub counter_bits: Option<u8>,
+    #[serde(default)]
+    pub channels: Option<u8>,
+    #[serde(default)]
+    pub complementary_channels: Option<u8>,
+    #[serde(default)]
+    pub fifo_depth: Option<u8>,
+    #[serde(default)]
+    pub resolutions: Vec<u8>,
+    #[serde(default)]
+    pub features: Vec<PeripheralFeature>,
+}
+
+#[derive(EnumDebug, Eq, PartialEq, Clone, Deserialize)]
+pub enum PeripheralFeature {
+    #[serde(rename = "fifo")]
+    Fifo,
+    #[serde(rename = "lin")]
+    Lin,
+    #[serde(rename = "smartcard")]
+    Smartcard,
+    #[serde(rename = "irda")]
+    Irda,
+    #[serde(rename = "auto_baud_rate")]
+    AutoBaudRate,
+    #[serde(rename = "driver_enable")]
+    DriverEnable,
+    #[serde(rename = "i2s")]
+    I2s,
+    #[serde(rename = "crc")]
+    Crc,
+    #[serde(rename = "break")]
+    Break,
+    #[serde(rename = "dithering")]
+    Dithering,
+    #[serde(rename = "oversampling")]
+    Oversampling,
+    #[serde(rename = "differential")]
+    Differential,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+pub struct PeripheralSecurityRegister {
+    pub register: String,
+    pub field: String,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
 pub struct PeripheralInterrupt {
     pub signal: String,
     pub interrupt: String,
+    pub exti_lines: Option<PeripheralExtiLines>,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
-pub struct PeripheralAfio {
+pub struct PeripheralExtiLines {
+    pub first: u8,
+    pub last: u8,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+pub struct PeripheralRemap {
+    pub peripheral: String,
     pub register: String,
     pub field: String,
     #[serde(default)]
-    pub values: Vec<PeripheralAfioValue>,
+    pub values: Vec<PeripheralRemapValue>,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
-pub struct PeripheralAfioValue {
+pub struct PeripheralRemapValue {
     pub value: u8,
     #[serde(default)]
     pub pins: Vec<String>,
 }
 
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PeripheralAfio {
+    pub register: String,
+    pub field: String,
+    pub values: Vec<PeripheralAfioValue>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PeripheralAfioValue {
+    pub value: u8,
+    pub pins: Vec<String>,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
 pub struct PeripheralRcc {
     pub bus_clock: String,
@@ -428,14 +651,66 @@ pub struct PeripheralRcc {
     #[serde(default)]
     pub reset: Option<PeripheralRccRegister>,
     #[serde(default)]
+    pub low_power_enable: Option<PeripheralRccRegister>,
+    #[serde(default)]
+    pub autonomous_enable: Option<PeripheralRccRegister>,
+    #[serde(default)]
     pub stop_mode: StopMode,
+    #[serde(default)]
+    pub low_power: Option<PeripheralRccLowPower>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+pub struct PeripheralRccLowPower {
+    #[serde(default)]
+    pub functional_in: Option<LowPowerMode>,
+    #[serde(default)]
+    pub wakeup: bool,
+    #[serde(default)]
+    pub retention: bool,
+    #[serde(default)]
+    pub backup_domain: bool,
+}
+
+#[derive(EnumDebug, Eq, PartialEq, Clone, Deserialize)]
+pub enum LowPowerMode {
+    #[serde(rename = "sleep")]
+    Sleep,
+    #[serde(rename = "stop")]
+    Stop,
+    #[serde(rename = "stop0")]
+    Stop0,
+    #[serde(rename = "stop1")]
+    Stop1,
+    #[serde(rename = "stop2")]
+    Stop2,
+    #[serde(rename = "stop3")]
+    Stop3,
+    #[serde(rename = "standby")]
+    Standby,
+    #[serde(rename = "shutdown")]
+    Shutdown,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
 #[serde(untagged)]
 pub enum PeripheralRccKernelClock {
     Clock(String),
-    Mux(PeripheralRccRegister),
+    Mux(PeripheralRccMux),
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+pub struct PeripheralRccMux {
+    pub register: String,
+    pub field: String,
+    #[serde(default)]
+    pub sources: Vec<PeripheralRccMuxSource>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+pub struct PeripheralRccMuxSource {
+    pub name: String,
+    pub value: u32,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
@@ -486,10 +761,33 @@ pub struct PeripheralDmaChannel {
     pub request: Option<u32>,
 }
 
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Hash)]
+pub struct PeripheralDmamuxInput {
+    pub dmamux: String,
+    pub signal: String,
+    pub sync_id: Option<u8>,
+    pub trigger_id: Option<u8>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Hash)]
+pub struct PeripheralDmaTrigger {
+    pub dma: String,
+    pub signal: String,
+    pub trigger: u8,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Deserialize, Hash)]
 pub struct PeripheralTrigger {
     pub signal: String,
     pub source: String,
+    pub field: Option<String>,
+    pub value: Option<u8>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Hash)]
+pub struct PeripheralInternalTrigger {
+    pub itr: u8,
+    pub source: String,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Deserialize, Hash)]
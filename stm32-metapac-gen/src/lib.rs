@@ -77,7 +77,22 @@ impl Gen {
             });
         }
 
+        // TrustZone chips get a second PAC with the peripherals at their secure alias,
+        // selected with the `secure` feature.
+        let secure_dev = core.peripherals.iter().any(|p| p.secure_address.is_some()).then(|| {
+            let mut dev = dev.clone();
+            for (ir_peri, p) in dev.peripherals.iter_mut().zip(&core.peripherals) {
+                ir_peri.base_address = p.secure_address.unwrap_or(p.address);
+            }
+            dev
+        });
+
         ir.devices.insert("".to_string(), dev);
+        let secure_ir = secure_dev.map(|dev| {
+            let mut ir = ir.clone();
+            ir.devices.insert("".to_string(), dev);
+            ir
+        });
 
         let mut extra = String::new();
 
@@ -91,9 +106,14 @@ impl Gen {
             .unwrap();
         }
 
-        // Cleanups!
-        transform::sort::Sort {}.run(&mut ir).unwrap();
-        transform::sanitize::Sanitize::default().run(&mut ir).unwrap();
+        if let Some(max) = core.interrupts.iter().map(|irq| irq.number).max() {
+            writeln!(
+                &mut extra,
+                "impl Interrupt {{ #[doc = \"Highest interrupt number.\"] pub const MAX: u16 = {}; }}",
+                max
+            )
+            .unwrap();
+        }
 
         // ==============================
         // Setup chip dir
@@ -106,17 +126,28 @@ impl Gen {
         fs::create_dir_all(&chip_dir).unwrap();
 
         // ==============================
-        // generate pac.rs
+        // generate pac.rs (and pac_secure.rs)
 
-        let data = generate::render(&ir, &gen_opts()).unwrap().to_string();
-        let data = data.replace("] ", "]\n");
+        let write_pac = |mut ir: ir::IR, file_name: &str| {
+            // Cleanups!
+            transform::sort::Sort {}.run(&mut ir).unwrap();
+            transform::sanitize::Sanitize::default().run(&mut ir).unwrap();
 
-        // Remove inner attributes like #![no_std]
-        let data = Regex::new("# *! *\\[.*\\]").unwrap().replace_all(&data, "");
+            let data = generate::render(&ir, &gen_opts()).unwrap().to_string();
+            let data = data.replace("] ", "]\n");
 
-        let mut file = File::create(chip_dir.join("pac.rs")).unwrap();
-        file.write_all(data.as_bytes()).unwrap();
-        file.write_all(extra.as_bytes()).unwrap();
+            // Remove inner attributes like #![no_std]
+            let data = Regex::new("# *! *\\[.*\\]").unwrap().replace_all(&data, "");
+
+            let mut file = File::create(chip_dir.join(file_name)).unwrap();
+            file.write_all(data.as_bytes()).unwrap();
+            file.write_all(extra.as_bytes()).unwrap();
+        };
+
+        write_pac(ir, "pac.rs");
+        if let Some(secure_ir) = secure_ir {
+            write_pac(secure_ir, "pac_secure.rs");
+        }
 
         let mut device_x = String::new();
 
@@ -185,10 +216,17 @@ impl Gen {
                 peripherals: PERIPHERALS,
                 nvic_priority_bits: {:?},
                 interrupts: INTERRUPTS,
+                vector_table: {},
                 dma_channels: DMA_CHANNELS,
                 pins: PINS,
             }};",
-            deduped_file, &chip.name, &chip.family, &chip.line, memories, &core.nvic_priority_bits,
+            deduped_file,
+            &chip.name,
+            &chip.family,
+            &chip.line,
+            memories,
+            &core.nvic_priority_bits,
+            stringify(&core.vector_table),
         );
 
         let mut file = File::create(chip_dir.join("metadata.rs")).unwrap();